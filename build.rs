@@ -0,0 +1,63 @@
+//! Generates the list of solutions that is linked into the `registry` binary.
+//! See `src/template/registry.rs` for how it is used.
+
+use std::{env, fs, path::Path};
+
+#[path = "src/template/event.rs"]
+mod event;
+
+fn main() {
+    // the list is only needed when building the registry binary, skip the work otherwise.
+    if env::var_os("CARGO_FEATURE_REGISTRY").is_none() {
+        println!("cargo:rerun-if-changed=build.rs");
+        return;
+    }
+
+    println!("cargo:rerun-if-changed=src/bin");
+    println!("cargo:rerun-if-changed=src/template/event.rs");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    // solutions are named `<year>-<day>.rs`, e.g. `2024-01.rs`.
+    let mut solutions: Vec<(u16, u8)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| {
+                    let name = entry.ok()?.file_name().into_string().ok()?;
+                    let (year, day) = name.strip_suffix(".rs")?.split_once('-')?;
+                    if year.len() != 4 || day.len() != 2 {
+                        return None;
+                    }
+                    Some((year.parse().ok()?, day.parse().ok()?))
+                })
+                .filter(|(year, day)| {
+                    *year >= event::FIRST_YEAR && (1..=event::days_in_event(*year)).contains(day)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    solutions.sort_unstable();
+
+    let mut out = String::new();
+
+    for (year, day) in &solutions {
+        let path = bin_dir.join(format!("{year}-{day:02}.rs"));
+        out.push_str(&format!(
+            "#[path = {path:?}]\n#[allow(dead_code)]\nmod y{year}_day_{day:02};\n"
+        ));
+    }
+
+    out.push_str("\nconst SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[\n");
+    for (year, day) in &solutions {
+        let module = format!("y{year}_day_{day:02}");
+        out.push_str(&format!(
+            "    advent_of_code::template::registry::Solution {{ year: {module}::YEAR, day: {module}::DAY, run: {module}::run_solution }},\n"
+        ));
+    }
+    out.push_str("];\n");
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("registry.rs");
+    fs::write(out_path, out).unwrap();
+}
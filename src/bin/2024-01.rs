@@ -1,9 +1,9 @@
 use std::collections::HashMap;
 
-advent_of_code::solution!(1);
+advent_of_code::solution!(1, parse = parse_input);
 
 fn parse_input(input: &str) -> (Vec<u64>, Vec<u64>) {
-    let (left, right): (Vec<u64>, Vec<u64>) = input
+    let (mut left, mut right): (Vec<u64>, Vec<u64>) = input
         .lines()
         .map(|line| {
             let mut split = line.split_whitespace();
@@ -22,26 +22,23 @@ fn parse_input(input: &str) -> (Vec<u64>, Vec<u64>) {
         })
         .unzip();
 
+    // part one pairs up the lists in order, part two does not care about the order.
+    left.sort();
+    right.sort();
+
     (left, right)
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let (mut left, mut right) = parse_input(input);
-
-    left.sort();
-    right.sort();
+pub fn part_one(input: &(Vec<u64>, Vec<u64>)) -> Option<u64> {
+    let (left, right) = input;
 
-    let sum = left
-        .into_iter()
-        .zip(right)
-        .map(|(l, r)| l.abs_diff(r))
-        .sum();
+    let sum = left.iter().zip(right).map(|(l, r)| l.abs_diff(*r)).sum();
 
     Some(sum)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let (left, right) = parse_input(input);
+pub fn part_two(input: &(Vec<u64>, Vec<u64>)) -> Option<u64> {
+    let (left, right) = input;
 
     let lookup_table = right.iter().fold(HashMap::new(), |mut acc, key| {
         // try to grab the count (value) at key (number),
@@ -69,13 +66,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(11));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(31));
     }
 }
@@ -1,4 +1,4 @@
-advent_of_code::solution!(2);
+advent_of_code::solution!(2, parse = parse_input);
 
 fn parse_input(input: &str) -> Vec<Vec<u64>> {
     input
@@ -11,11 +11,9 @@ fn parse_input(input: &str) -> Vec<Vec<u64>> {
         .collect()
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let data = parse_input(input);
-
+pub fn part_one(data: &[Vec<u64>]) -> Option<u64> {
     let count = data
-        .into_iter()
+        .iter()
         .map(|row| {
             // build an iterator over adjacent pairs
             // and compute the differences from those
@@ -95,9 +93,7 @@ fn is_row_safe(row: &[u64], allowed_outliers: usize) -> bool {
     }
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let data = parse_input(input);
-
+pub fn part_two(data: &[Vec<u64>]) -> Option<u64> {
     let allowed_outliers = 1;
 
     let count = data
@@ -115,13 +111,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(2));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(4));
     }
 }
@@ -1,6 +1,6 @@
 use std::{cmp::Ordering, collections::HashMap};
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, parse = parse_input);
 
 type PageOrderingRules = HashMap<u64, Vec<PageUpdate>>;
 type PageUpdate = u64;
@@ -62,32 +62,32 @@ fn is_sorted(rules: &PageOrderingRules, updates: &[PageUpdate]) -> bool {
     true
 }
 
-fn get_middle_number(updates: &[PageUpdate]) -> Option<u64> {
-    let length = updates.len();
-
+/// Find the middle of `length` updates, `get` returns the update at an index.
+fn get_middle_number(length: usize, get: impl Fn(usize) -> Option<u64>) -> Option<u64> {
     if length % 2 == 0 {
         // compute the mean of the two elements surrounding the middle
         let idx_right = length / 2;
         let idx_left = idx_right - 1;
 
-        updates
-            .get(idx_left)
-            .zip(updates.get(idx_right))
+        get(idx_left)
+            .zip(get(idx_right))
             .map(|(left, right)| (left + right) / 2)
     } else {
         // division on usizes automatically floors the value
         let idx = length / 2;
-        updates.get(idx).cloned()
+        get(idx)
     }
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let (rules, updates) = parse_input(input);
+pub fn part_one(input: &(PageOrderingRules, Vec<Vec<PageUpdate>>)) -> Option<u64> {
+    let (rules, updates) = input;
 
     let sum = updates
         .iter()
-        .filter(|update_row| is_sorted(&rules, update_row))
-        .filter_map(|update_row| get_middle_number(update_row))
+        .filter(|update_row| is_sorted(rules, update_row))
+        .filter_map(|update_row| {
+            get_middle_number(update_row.len(), |idx| update_row.get(idx).copied())
+        })
         .sum();
 
     Some(sum)
@@ -108,19 +108,29 @@ fn sort_updates(rules: &PageOrderingRules, a: &u64, b: &u64) -> Ordering {
     Ordering::Equal
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let (rules, updates) = parse_input(input);
+/// The update at `idx` once `updates` is sorted, without sorting them:
+/// it is the update that exactly `idx` of the other updates have to come before.
+fn get_sorted_update(rules: &PageOrderingRules, updates: &[PageUpdate], idx: usize) -> Option<u64> {
+    updates.iter().copied().find(|update| {
+        updates
+            .iter()
+            .filter(|other| sort_updates(rules, other, update) == Ordering::Less)
+            .count()
+            == idx
+    })
+}
+
+pub fn part_two(input: &(PageOrderingRules, Vec<Vec<PageUpdate>>)) -> Option<u64> {
+    let (rules, updates) = input;
 
     let sum = updates
-        .into_iter()
-        .filter(|update_row| !is_sorted(&rules, update_row))
-        .map(|mut update_row| {
-            // sort our update rows
-            update_row.sort_by(|a, b| sort_updates(&rules, a, b));
-
-            update_row
+        .iter()
+        .filter(|update_row| !is_sorted(rules, update_row))
+        .filter_map(|update_row| {
+            get_middle_number(update_row.len(), |idx| {
+                get_sorted_update(rules, update_row, idx)
+            })
         })
-        .filter_map(|update_row| get_middle_number(&update_row))
         .sum();
 
     Some(sum)
@@ -132,13 +142,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(143));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(123));
     }
 }
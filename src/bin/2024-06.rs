@@ -1,6 +1,6 @@
 use std::{collections::HashSet, str::FromStr};
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(6, parse = parse_input);
 
 #[derive(Debug, Clone)]
 enum Direction {
@@ -51,17 +51,31 @@ impl TryFrom<char> for Field {
 }
 
 #[derive(Debug, Clone)]
-struct Grid {
+pub struct Grid {
     grid: Vec<Vec<Field>>,
 
     initial_guard_position: (usize, usize),
     initial_guard_direction: Direction,
+}
+
+/// The positions the guard walks through, starting from the initial position of the grid.
+struct Walk<'a> {
+    grid: &'a [Vec<Field>],
 
     current_guard_position: (usize, usize),
     current_guard_direction: Direction,
 }
 
 impl Grid {
+    fn walk(&self) -> Walk<'_> {
+        Walk {
+            grid: &self.grid,
+
+            current_guard_position: self.initial_guard_position,
+            current_guard_direction: self.initial_guard_direction.clone(),
+        }
+    }
+
     fn get_guard_info(grid: &[Vec<Field>]) -> Option<((usize, usize), Direction)> {
         for (x, row) in grid.iter().enumerate() {
             for (y, col) in row.iter().enumerate() {
@@ -95,16 +109,13 @@ impl FromStr for Grid {
         Ok(Grid {
             grid,
 
-            initial_guard_position: guard_position.clone(),
-            initial_guard_direction: guard_direction.clone(),
-
-            current_guard_position: guard_position,
-            current_guard_direction: guard_direction,
+            initial_guard_position: guard_position,
+            initial_guard_direction: guard_direction,
         })
     }
 }
 
-impl Iterator for Grid {
+impl Iterator for Walk<'_> {
     type Item = (usize, usize);
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -167,11 +178,13 @@ impl Iterator for Grid {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let grid = Grid::from_str(input).unwrap();
+fn parse_input(input: &str) -> Grid {
+    Grid::from_str(input).unwrap()
+}
 
+pub fn part_one(grid: &Grid) -> Option<u64> {
     let positions: HashSet<(usize, usize)> = grid
-        .into_iter()
+        .walk()
         // .inspect(|x| println!("pos: {:?}", x))
         .collect();
     let positions_visited = positions.len();
@@ -179,7 +192,7 @@ pub fn part_one(input: &str) -> Option<u64> {
     Some(positions_visited as u64)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
+pub fn part_two(grid: &Grid) -> Option<u64> {
     None
 }
 
@@ -189,13 +202,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(41));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, None);
     }
 }
@@ -1,6 +1,6 @@
 use itertools::Itertools;
 
-advent_of_code::solution!(7);
+advent_of_code::solution!(7, parse = parse_input);
 
 #[derive(Debug, Clone)]
 enum Operator {
@@ -63,11 +63,9 @@ fn evaluate(operators: &[Operator], values: &[u64]) -> u64 {
         })
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let rows = parse_input(input);
-
+pub fn part_one(rows: &[(u64, Vec<u64>)]) -> Option<u64> {
     let sum = rows
-        .into_iter()
+        .iter()
         .filter(|(result, values)| {
             // generate all combinations of operators
             // length should be values.len() - 1
@@ -91,7 +89,7 @@ pub fn part_one(input: &str) -> Option<u64> {
     Some(sum)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
+pub fn part_two(rows: &[(u64, Vec<u64>)]) -> Option<u64> {
     None
 }
 
@@ -101,13 +99,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, Some(3749));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse_input(&advent_of_code::template::read_file(
+            "examples", DAY,
+        )));
         assert_eq!(result, None);
     }
 }
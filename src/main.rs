@@ -1,78 +1,359 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, answer, download, read, scaffold, session, solve, time,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::{Day, Year};
 #[cfg(feature = "today")]
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        all_days,
+        profile::Profile,
+        runner::{parse_duration, BenchConfig, Limits, OutputFormat},
+        Day, Year,
+    };
+    use std::{env, process, time::Duration};
+
+    /// The minimum time between two requests when downloading several days.
+    const DOWNLOAD_DELAY: Duration = Duration::from_secs(5);
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            year: Year,
+            days: Vec<Day>,
+            all: bool,
+            force: bool,
+            delay: Duration,
         },
         Read {
+            year: Year,
             day: Day,
         },
         Scaffold {
+            year: Year,
             day: Day,
             download: bool,
             overwrite: bool,
+            refresh_tests: bool,
+            dry_run: bool,
         },
         Solve {
+            year: Year,
             day: Day,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            yes: bool,
+            bench_config: Option<BenchConfig>,
+            count_allocs: bool,
+            spans: bool,
+            format: OutputFormat,
+            all_inputs: bool,
         },
         All {
+            year: Year,
             release: bool,
+            format: OutputFormat,
+            jobs: usize,
+            limits: Limits,
+            verify: bool,
         },
         Time {
+            year: Year,
             all: bool,
             day: Option<Day>,
             store: bool,
+            bench_config: BenchConfig,
+            compare_threshold: Option<f64>,
+            count_allocs: bool,
+            format: OutputFormat,
+            limits: Limits,
+        },
+        History {
+            year: Year,
+            day: Day,
+        },
+        Answer {
+            year: Year,
+            day: Day,
+            part: u8,
+            answer: String,
+        },
+        Session {
+            year: Year,
+            check: bool,
+            list: bool,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
+    fn parse_bench_config(
+        args: &mut pico_args::Arguments,
+    ) -> Result<BenchConfig, Box<dyn std::error::Error>> {
+        let default = BenchConfig::default();
+
+        let bench_config = BenchConfig {
+            warmup: args
+                .opt_value_from_fn("--warmup", parse_duration)?
+                .unwrap_or(default.warmup),
+            budget: args
+                .opt_value_from_fn("--budget", parse_duration)?
+                .unwrap_or(default.budget),
+            min_samples: args
+                .opt_value_from_str("--min-samples")?
+                .unwrap_or(default.min_samples),
+            max_samples: args
+                .opt_value_from_str("--max-samples")?
+                .unwrap_or(default.max_samples),
+        };
+
+        bench_config.validate()?;
+
+        Ok(bench_config)
+    }
+
+    /// The year passed with `--year`, falling back to `AOC_YEAR` and the most recent event.
+    fn parse_year(args: &mut pico_args::Arguments) -> Result<Year, Box<dyn std::error::Error>> {
+        Ok(args
+            .opt_value_from_str("--year")?
+            .or_else(Year::from_env)
+            .unwrap_or_else(Year::latest))
+    }
+
+    /// Parse a day, checking that it is part of the event of `year`.
+    fn parse_day(
+        args: &mut pico_args::Arguments,
+        year: Year,
+    ) -> Result<Day, Box<dyn std::error::Error>> {
+        let day: String = args.free_from_str()?;
+        Ok(Day::parse_in(year, &day)?)
+    }
+
+    /// Parse a day or an inclusive range of days like `1-5`.
+    fn parse_days(
+        args: &mut pico_args::Arguments,
+        year: Year,
+    ) -> Result<Vec<Day>, Box<dyn std::error::Error>> {
+        let days: String = args.free_from_str()?;
+
+        let Some((first, last)) = days.split_once('-') else {
+            return Ok(vec![Day::parse_in(year, &days)?]);
+        };
+
+        let first = Day::parse_in(year, first)?;
+        let last = Day::parse_in(year, last)?;
+
+        if first > last {
+            return Err(
+                format!("expecting the range `{days}` to start with the lower day.").into(),
+            );
+        }
+
+        Ok(all_days(year)
+            .filter(|day| (first..=last).contains(day))
+            .collect())
+    }
+
+    fn parse_format(
+        args: &mut pico_args::Arguments,
+    ) -> Result<OutputFormat, Box<dyn std::error::Error>> {
+        Ok(args.opt_value_from_str("--format")?.unwrap_or_default())
+    }
+
+    fn parse_limits(args: &mut pico_args::Arguments) -> Result<Limits, Box<dyn std::error::Error>> {
+        let timeout: Option<f64> = args.opt_value_from_str("--timeout")?;
+        let memory_mib: Option<u64> = args.opt_value_from_str("--memory-limit")?;
+
+        if timeout.is_some_and(|secs| !secs.is_finite() || secs <= 0_f64) {
+            return Err("expecting `--timeout` to be a positive number of seconds.".into());
+        }
+
+        if memory_mib.is_some() && cfg!(not(target_os = "linux")) {
+            return Err("`--memory-limit` is only supported on Linux.".into());
+        }
+
+        Ok(Limits {
+            timeout: timeout.map(Duration::from_secs_f64),
+            memory: memory_mib.map(|mib| mib * 1024 * 1024),
+        })
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
+        let subcommand = args.subcommand()?;
+        let year = parse_year(&mut args)?;
+
+        // NOTE: the flag is passed on as an environment variable, so it also applies to the solutions run by `solve`.
+        if args.contains("--offline") {
+            env::set_var("AOC_OFFLINE", "1");
+        }
+
+        // NOTE: the selected profile is passed on the same way, so solutions read the input of the profile.
+        if let Some(profile) = args.opt_value_from_str::<_, String>("--profile")? {
+            if Profile::new(&profile).is_none() {
+                return Err(format!(
+                    "expecting profile `{profile}` to only contain letters, digits, `-` and `_`."
+                )
+                .into());
+            }
+            env::set_var("AOC_PROFILE", profile);
+        }
+
+        let app_args = match subcommand.as_deref() {
+            Some("all") => {
+                let jobs = args.opt_value_from_str(["-j", "--jobs"])?.unwrap_or(1);
+
+                if jobs == 0 {
+                    return Err("expecting `--jobs` to be at least 1.".into());
+                }
+
+                AppArguments::All {
+                    year,
+                    release: args.contains("--release"),
+                    format: parse_format(&mut args)?,
+                    jobs,
+                    limits: parse_limits(&mut args)?,
+                    verify: args.contains("--verify"),
+                }
+            }
+            Some("time") if args.contains("--history") => AppArguments::History {
+                year,
+                day: parse_day(&mut args, year)?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let compare = args.contains("--compare");
+                let threshold: Option<f64> = args.opt_value_from_str("--threshold")?;
+
+                if threshold.is_some() && !compare {
+                    return Err("`--threshold` can only be used with `--compare`.".into());
+                }
+
+                if threshold.is_some_and(|percent| !percent.is_finite() || percent < 0_f64) {
+                    return Err("expecting `--threshold` to be a percentage of at least 0.".into());
+                }
+
+                let count_allocs = args.contains("--allocs");
+                let format = parse_format(&mut args)?;
+                let limits = parse_limits(&mut args)?;
+                let bench_config = parse_bench_config(&mut args)?;
 
                 AppArguments::Time {
+                    year,
                     all,
-                    day: args.opt_free_from_str()?,
+                    day: args
+                        .opt_free_from_str::<String>()?
+                        .map(|day| Day::parse_in(year, &day))
+                        .transpose()?,
                     store,
+                    bench_config,
+                    compare_threshold: compare.then(|| threshold.unwrap_or(10_f64)),
+                    count_allocs,
+                    format,
+                    limits,
+                }
+            }
+            Some("answer") => {
+                let day = parse_day(&mut args, year)?;
+                let part = args.free_from_str()?;
+                let answer = args.free_from_str()?;
+
+                if !matches!(part, 1 | 2) {
+                    return Err("expecting part to be `1` or `2`.".into());
+                }
+
+                AppArguments::Answer {
+                    year,
+                    day,
+                    part,
+                    answer,
+                }
+            }
+            Some("download") => {
+                let all = args.contains("--all");
+                let force = args.contains("--force");
+                let delay = args
+                    .opt_value_from_fn("--delay", parse_duration)?
+                    .unwrap_or(DOWNLOAD_DELAY);
+
+                AppArguments::Download {
+                    year,
+                    days: if all {
+                        all_days(year).collect()
+                    } else {
+                        parse_days(&mut args, year)?
+                    },
+                    all,
+                    force,
+                    delay,
                 }
             }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
-            },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                year,
+                day: parse_day(&mut args, year)?,
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                year,
+                day: parse_day(&mut args, year)?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                refresh_tests: args.contains("--refresh-tests"),
+                dry_run: args.contains("--dry-run"),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+            Some("solve") => {
+                let time = args.contains("--time");
+                let dhat = args.contains("--dhat");
+                let count_allocs = args.contains("--allocs");
+                let spans = args.contains("--spans");
+                let all_inputs = args.contains("--all-inputs");
+                let format = parse_format(&mut args)?;
+                let bench_config = parse_bench_config(&mut args)?;
+                let submit = args.opt_value_from_str("--submit")?;
+                let yes = args.contains("--yes");
+
+                if yes && submit.is_none() {
+                    return Err("`--yes` can only be used with `--submit`.".into());
+                }
+
+                if dhat && count_allocs {
+                    return Err("`--dhat` and `--allocs` cannot be combined.".into());
+                }
+
+                if all_inputs && (submit.is_some() || time || dhat || spans) {
+                    return Err("`--all-inputs` cannot be combined with `--submit`, `--time`, `--dhat` or `--spans`.".into());
+                }
+
+                if all_inputs && format != OutputFormat::Human {
+                    return Err(
+                        "`--all-inputs` only supports the human-readable output format.".into(),
+                    );
+                }
+
+                AppArguments::Solve {
+                    year,
+                    day: parse_day(&mut args, year)?,
+                    release: args.contains("--release"),
+                    submit,
+                    yes,
+                    dhat,
+                    bench_config: time.then_some(bench_config),
+                    count_allocs,
+                    spans,
+                    format,
+                    all_inputs,
+                }
+            }
+            // NOTE: `cargo login` is a built-in command that cannot be aliased, `cargo session` runs `login`.
+            Some("login" | "session") => AppArguments::Session {
+                year,
+                check: args.contains("--check"),
+                list: args.contains("--list"),
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -102,38 +383,132 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::All {
+                year,
+                release,
+                format,
+                jobs,
+                limits,
+                verify,
+            } => all::handle(year, release, format, jobs, limits, verify),
+            AppArguments::Time {
+                year,
+                day,
+                all,
+                store,
+                bench_config,
+                compare_threshold,
+                count_allocs,
+                format,
+                limits,
+            } => time::handle(
+                year,
+                day,
+                all,
+                store,
+                &bench_config,
+                compare_threshold,
+                count_allocs,
+                format,
+                &limits,
+            ),
+            AppArguments::History { year, day } => time::handle_history(year, day),
+            AppArguments::Answer {
+                year,
+                day,
+                part,
+                answer,
+            } => answer::handle(year, day, part, &answer),
+            AppArguments::Session { list: true, .. } => session::handle_list(),
+            AppArguments::Session {
+                year, check: true, ..
+            } => session::handle_check(year),
+            AppArguments::Session { year, .. } => session::handle(year),
+            AppArguments::Download {
+                year,
+                days,
+                all,
+                force,
+                delay,
+            } => match days.as_slice() {
+                [day] if !all => download::handle(year, *day, force),
+                _ => download::handle_many(year, &days, force, delay),
+            },
+            AppArguments::Read { year, day } => read::handle(year, day),
             AppArguments::Scaffold {
+                year,
                 day,
                 download,
-                overwrite,
+                refresh_tests: true,
+                dry_run,
+                ..
             } => {
-                scaffold::handle(day, overwrite);
+                // NOTE: only the tests of an existing module are updated, nothing is scaffolded.
+                if dry_run {
+                    if download {
+                        println!("Would download the input and puzzle of day {day}.");
+                    }
+                    println!("Would update the tests with the example answers of the puzzle.");
+                    return;
+                }
                 if download {
-                    download::handle(day);
+                    download::handle(year, day, false);
                 }
+                scaffold::refresh_tests(year, day);
             }
+            AppArguments::Scaffold {
+                year,
+                day,
+                download,
+                overwrite,
+                dry_run,
+                ..
+            } => scaffold::handle(year, day, overwrite, download, dry_run),
             AppArguments::Solve {
+                year,
+                day,
+                release,
+                count_allocs,
+                all_inputs: true,
+                ..
+            } => solve::handle_all_inputs(year, day, release, count_allocs),
+            AppArguments::Solve {
+                year,
+                day,
+                release,
+                dhat,
+                submit,
+                yes,
+                bench_config,
+                count_allocs,
+                spans,
+                format,
+                all_inputs: false,
+            } => solve::handle(
+                year,
                 day,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                yes,
+                bench_config.as_ref(),
+                count_allocs,
+                spans,
+                format,
+            ),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                        // NOTE: `today` always targets the current event, regardless of `AOC_YEAR`.
+                        let year = Year::latest();
+                        scaffold::handle(year, day, false, true, false);
+                        read::handle(year, day)
                     }
                     None => {
                         eprintln!(
                             "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
+                            the last day of the event in december. Please use `scaffold` with a specific day."
                         );
                         process::exit(1)
                     }
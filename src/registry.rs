@@ -0,0 +1,13 @@
+//! Runs all solutions in a single process, used by `cargo all` and `cargo time`.
+//! Built with the `registry` feature, the linked solutions are generated by `build.rs`.
+
+include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+
+#[cfg(feature = "count-allocs")]
+#[global_allocator]
+static COUNTING_ALLOC: advent_of_code::template::allocations::CountingAlloc =
+    advent_of_code::template::allocations::CountingAlloc;
+
+fn main() {
+    advent_of_code::template::registry::run(SOLUTIONS);
+}
@@ -0,0 +1,76 @@
+/// A global allocator that counts allocations, used to report the memory profile of a part next to its timing.
+/// It is installed by the `solution!` macro when the `count-allocs` feature is enabled.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::template::timings::Allocs;
+
+static COUNT: AtomicUsize = AtomicUsize::new(0);
+static TOTAL_BYTES: AtomicUsize = AtomicUsize::new(0);
+static CURRENT_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+static BASELINE_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+pub struct CountingAlloc;
+
+impl CountingAlloc {
+    fn record(size: usize) {
+        COUNT.fetch_add(1, Ordering::Relaxed);
+        TOTAL_BYTES.fetch_add(size, Ordering::Relaxed);
+        let current = CURRENT_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK_BYTES.fetch_max(current, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::record(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::record(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            // NOTE: a realloc counts as a new allocation of `new_size` that frees the old one.
+            CURRENT_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+            Self::record(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Reset the counters, memory that is already allocated does not count towards the peak.
+pub fn reset() {
+    let current = CURRENT_BYTES.load(Ordering::Relaxed);
+    COUNT.store(0, Ordering::Relaxed);
+    TOTAL_BYTES.store(0, Ordering::Relaxed);
+    BASELINE_BYTES.store(current, Ordering::Relaxed);
+    PEAK_BYTES.store(current, Ordering::Relaxed);
+}
+
+/// Read the counters since the last call to [`reset`].
+pub fn snapshot() -> Allocs {
+    let baseline = BASELINE_BYTES.load(Ordering::Relaxed);
+
+    Allocs {
+        count: COUNT.load(Ordering::Relaxed) as u64,
+        total_bytes: TOTAL_BYTES.load(Ordering::Relaxed) as u64,
+        peak_bytes: PEAK_BYTES.load(Ordering::Relaxed).saturating_sub(baseline) as u64,
+    }
+}
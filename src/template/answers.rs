@@ -0,0 +1,185 @@
+/// Known answers to the real inputs, used to verify solutions after a refactor.
+/// Answers are recorded after a correct submission or with `cargo answer`.
+use std::{collections::HashMap, fs, io, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::profile::{self, Profile};
+use crate::template::{Day, Year};
+
+/// Known answers per day, can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub data: HashMap<Day, [Option<String>; 2]>,
+}
+
+/// Outcome of comparing the output of a part with its known answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verification {
+    Correct,
+    Incorrect { expected: String },
+    Unknown,
+}
+
+impl Answers {
+    /// The file that holds the answers of a year, per session profile.
+    pub fn path(year: Year) -> String {
+        year.data_path(&profile::scoped("answers.json"))
+    }
+
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), io::Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(Self::path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    pub fn read_from_file(year: Year) -> Self {
+        Self::read_for(year, Profile::active().as_ref())
+    }
+
+    /// Rehydrate the answers of a profile, e.g. to verify the input of a teammate.
+    pub fn read_for(year: Year, profile: Option<&Profile>) -> Self {
+        fs::read_to_string(year.data_path(&profile::scoped_to("answers.json", profile)))
+            .map_err(|x| x.to_string())
+            .and_then(|s| Answers::try_from(s.as_str()))
+            .unwrap_or_default()
+    }
+
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        self.data.get(&day)?.get(Self::index(part)?)?.as_deref()
+    }
+
+    /// Set the answer of a part, returns the previous answer if there was one.
+    pub fn set(&mut self, day: Day, part: u8, answer: &str) -> Option<String> {
+        let index = Self::index(part)?;
+        self.data.entry(day).or_default()[index].replace(answer.into())
+    }
+
+    /// Compare the output of a part with its known answer.
+    /// A part that did not produce an answer is incorrect if an answer is known.
+    pub fn verify(&self, day: Day, part: u8, answer: Option<&str>) -> Verification {
+        match self.get(day, part) {
+            None => Verification::Unknown,
+            Some(expected) if Some(expected) == answer => Verification::Correct,
+            Some(expected) => Verification::Incorrect {
+                expected: expected.into(),
+            },
+        }
+    }
+
+    fn index(part: u8) -> Option<usize> {
+        match part {
+            1 => Some(0),
+            2 => Some(1),
+            _ => None,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answers> for JsonValue {
+    fn from(value: &Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        for (day, [part_1, part_2]) in &value.data {
+            let mut parts: HashMap<String, JsonValue> = HashMap::new();
+
+            for (key, answer) in [("part_1", part_1), ("part_2", part_2)] {
+                parts.insert(
+                    key.into(),
+                    answer.clone().map_or(JsonValue::Null, JsonValue::String),
+                );
+            }
+
+            map.insert(day.to_string(), JsonValue::Object(parts));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&str> for Answers {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON file."))?;
+
+        let days = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let mut data = HashMap::new();
+
+        for (day, parts) in days {
+            let day = Day::from_str(day).map_err(|_| format!("expected `{day}` to be a day."))?;
+
+            let parts = parts
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("expected answers of a day to be an object.")?;
+
+            let answer = |key: &str| match parts.get(key) {
+                Some(JsonValue::String(answer)) => Ok(Some(answer.clone())),
+                Some(JsonValue::Null) | None => Ok(None),
+                Some(_) => Err(format!("expected {day}.{key} to be null or string.")),
+            };
+
+            data.insert(day, [answer("part_1")?, answer("part_2")?]);
+        }
+
+        Ok(Answers { data })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answers, Verification};
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn roundtrips_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), 1, "11");
+        answers.set(day!(5), 2, "multi\nline");
+
+        let json = JsonValue::from(&answers).stringify().unwrap();
+        assert_eq!(Answers::try_from(json.as_str()), Ok(answers));
+    }
+
+    #[test]
+    fn sets_answers() {
+        let mut answers = Answers::default();
+        assert_eq!(answers.set(day!(1), 1, "11"), None);
+        assert_eq!(answers.set(day!(1), 1, "12"), Some("11".into()));
+        assert_eq!(answers.set(day!(1), 3, "13"), None);
+        assert_eq!(answers.get(day!(1), 1), Some("12"));
+        assert_eq!(answers.get(day!(1), 2), None);
+        assert_eq!(answers.get(day!(1), 3), None);
+    }
+
+    #[test]
+    fn verifies_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), 1, "11");
+
+        assert_eq!(
+            answers.verify(day!(1), 1, Some("11")),
+            Verification::Correct
+        );
+        assert_eq!(
+            answers.verify(day!(1), 1, Some("12")),
+            Verification::Incorrect {
+                expected: "11".into()
+            }
+        );
+        assert_eq!(
+            answers.verify(day!(1), 1, None),
+            Verification::Incorrect {
+                expected: "11".into()
+            }
+        );
+        assert_eq!(answers.verify(day!(1), 2, Some("1")), Verification::Unknown);
+    }
+}
@@ -1,12 +1,16 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
+    env,
     fmt::Display,
-    process::{Command, Output, Stdio},
+    fs,
+    process::{self, Command, Output, Stdio},
 };
 
-use crate::template::Day;
+use crate::template::aoc_client::{AocClient, AocClientError};
+use crate::template::profile::Profile;
+use crate::template::{Day, Year};
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub enum AocCommandError {
     CommandNotFound,
     CommandNotCallable,
@@ -18,8 +22,14 @@ impl Display for AocCommandError {
         match self {
             AocCommandError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
             AocCommandError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
-            AocCommandError::BadExitStatus(_) => {
-                write!(f, "aoc-cli exited with a non-zero status.")
+            AocCommandError::BadExitStatus(output) => {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                match stderr.lines().rev().find(|l| !l.trim().is_empty()) {
+                    Some(line) => {
+                        write!(f, "aoc-cli exited with a non-zero status: {}", line.trim())
+                    }
+                    None => write!(f, "aoc-cli exited with a non-zero status."),
+                }
             }
         }
     }
@@ -33,87 +43,92 @@ pub fn check() -> Result<(), AocCommandError> {
     Ok(())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
+/// Client that wraps the aoc-cli command-line. Files are downloaded to a temporary location and read back.
+pub struct AocCli {
+    year: Year,
+}
 
-    let args = build_args(
-        "read",
-        &[
-            "--description-only".into(),
-            "--puzzle-file".into(),
-            puzzle_path,
-        ],
-        day,
-    );
+impl AocCli {
+    pub fn new(year: Year) -> Self {
+        Self { year }
+    }
+}
+
+impl AocClient for AocCli {
+    fn input(&self, day: Day) -> Result<String, AocClientError> {
+        download(self.year, day, "--input-only", "--input-file")
+    }
+
+    fn puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        download(self.year, day, "--puzzle-only", "--puzzle-file")
+    }
+
+    fn submit(&self, day: Day, part: u8, answer: &str) -> Result<String, AocClientError> {
+        check()?;
+
+        // workaround: the argument order is inverted for submit.
+        let mut args = build_args("submit", &[], self.year, day);
+        args.push(part.to_string());
+        args.push(answer.to_string());
 
-    call_aoc_cli(&args)
+        // NOTE: capture the output, so callers can parse the verdict of the submission.
+        let output = call_aoc_cli(&args, Stdio::piped())?;
+        let response = String::from_utf8_lossy(&output.stdout).into_owned();
+
+        match AocClientError::from_response(&response) {
+            Some(e) => Err(e),
+            None => Ok(response),
+        }
+    }
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
+fn download(year: Year, day: Day, only: &str, file_arg: &str) -> Result<String, AocClientError> {
+    check()?;
+
+    let path = env::temp_dir().join(format!("aoc-{}-{day}{only}", process::id()));
 
     let args = build_args(
         "download",
         &[
             "--overwrite".into(),
-            "--input-file".into(),
-            input_path.to_string(),
-            "--puzzle-file".into(),
-            puzzle_path.to_string(),
+            only.into(),
+            file_arg.into(),
+            path.to_string_lossy().into_owned(),
         ],
+        year,
         day,
     );
 
-    let output = call_aoc_cli(&args)?;
-    println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-    Ok(output)
-}
-
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
-    // workaround: the argument order is inverted for submit.
-    let mut args = build_args("submit", &[], day);
-    args.push(part.to_string());
-    args.push(result.to_string());
-    call_aoc_cli(&args)
-}
+    call_aoc_cli(&args, Stdio::null())?;
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| AocClientError::Request(format!("could not read downloaded file: {e}")));
+    let _ = fs::remove_file(&path);
+    contents
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
-}
-
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
-}
-
-fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
+fn build_args(command: &str, args: &[String], year: Year, day: Day) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
-    if let Some(year) = get_year() {
-        cmd_args.push("--year".into());
-        cmd_args.push(year.to_string());
+    if let Some(path) = Profile::active().and_then(|profile| profile.session_path()) {
+        cmd_args.push("--session-file".into());
+        cmd_args.push(path.to_string_lossy().into_owned());
     }
 
+    cmd_args.push("--year".into());
+    cmd_args.push(year.to_string());
+
     cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
 
     cmd_args
 }
 
-fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
+fn call_aoc_cli(args: &[String], stdout: Stdio) -> Result<Output, AocCommandError> {
     // println!("Calling >aoc with: {}", args.join(" "));
     let output = Command::new("aoc")
         .args(args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
+        .stdout(stdout)
+        .stderr(Stdio::piped())
         .output()
         .map_err(|_| AocCommandError::CommandNotCallable)?;
 
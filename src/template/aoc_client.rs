@@ -0,0 +1,492 @@
+/// Clients that talk to the Advent of Code website.
+/// The native client is used by default, the aoc-cli wrapper is kept as a fallback.
+use std::{
+    cell::Cell,
+    env,
+    fmt::Display,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::template::aoc_cli::{self, AocCli, AocCommandError};
+use crate::template::profile::{self, Profile};
+use crate::template::{html, Day, Year};
+
+static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+static USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+/// Operations that are needed to download puzzles and submit answers.
+pub trait AocClient {
+    /// The personal puzzle input of a day.
+    fn input(&self, day: Day) -> Result<String, AocClientError>;
+
+    /// The puzzle description of a day, as markdown.
+    fn puzzle(&self, day: Day) -> Result<String, AocClientError>;
+
+    /// Submit an answer, returns the response as text.
+    fn submit(&self, day: Day, part: u8, answer: &str) -> Result<String, AocClientError>;
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AocClientError {
+    SessionNotFound,
+    SessionExpired,
+    NotUnlocked,
+    RateLimited {
+        wait: Option<String>,
+    },
+    AlreadySolved,
+    /// Requests are disabled with `--offline` or `AOC_OFFLINE`.
+    Offline,
+    AocCli(AocCommandError),
+    Request(String),
+}
+
+impl AocClientError {
+    /// Detect known errors in a response of the website or in the output of aoc-cli.
+    pub fn from_response(text: &str) -> Option<Self> {
+        let lowercase = text.to_lowercase();
+
+        if text.contains("You gave an answer too recently") {
+            let wait = text
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .map(|(wait, _)| wait.to_string());
+            Some(AocClientError::RateLimited { wait })
+        } else if text.contains("You don't seem to be solving the right level") {
+            Some(AocClientError::AlreadySolved)
+        } else if lowercase.contains("before it unlocks") || lowercase.contains("not available yet")
+        {
+            Some(AocClientError::NotUnlocked)
+        } else if lowercase.contains("please log in") || lowercase.contains("identify yourself") {
+            Some(AocClientError::SessionExpired)
+        } else if lowercase.contains("session cookie") {
+            Some(AocClientError::SessionNotFound)
+        } else {
+            None
+        }
+    }
+
+    /// A suggestion how to fix the error, if there is one.
+    pub fn suggestion(&self) -> Option<String> {
+        let suggestion = match self {
+            AocClientError::SessionNotFound => match Profile::active() {
+                Some(profile) => format!("run `cargo session --profile {profile}` to store its session cookie."),
+                None => "copy the `session` cookie of the website to \"~/.adventofcode.session\" or set ADVENT_OF_CODE_SESSION.".into(),
+            },
+            AocClientError::SessionExpired => match Profile::active() {
+                Some(profile) => format!("log in to the website again and run `cargo session --profile {profile}` with the new session cookie."),
+                None => "log in to the website again and update the session cookie in \"~/.adventofcode.session\" or ADVENT_OF_CODE_SESSION.".into(),
+            },
+            AocClientError::NotUnlocked => "puzzles unlock at midnight EST (UTC-5), check the day and AOC_YEAR in \".cargo/config.toml\".".into(),
+            AocClientError::RateLimited { wait: Some(wait) } => format!("wait {wait} before submitting again."),
+            AocClientError::RateLimited { wait: None } => "wait a minute before submitting again.".into(),
+            AocClientError::AlreadySolved => "check the part passed to `--submit`. To record the answer of a solved part, use `cargo answer <day> <part> <answer>`.".into(),
+            AocClientError::Offline => "drop `--offline` and unset AOC_OFFLINE to allow requests.".into(),
+            AocClientError::AocCli(AocCommandError::CommandNotFound) => "install aoc-cli with `cargo install aoc-cli`, or configure a session cookie to use the built-in client.".into(),
+            AocClientError::AocCli(_) | AocClientError::Request(_) => return None,
+        };
+        Some(suggestion)
+    }
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(f, "no session cookie found."),
+            AocClientError::SessionExpired => {
+                write!(f, "the session cookie is invalid or expired.")
+            }
+            AocClientError::NotUnlocked => write!(f, "the puzzle is not unlocked yet."),
+            AocClientError::RateLimited { wait: Some(wait) } => {
+                write!(f, "you gave an answer too recently, {wait} left to wait.")
+            }
+            AocClientError::RateLimited { wait: None } => {
+                write!(f, "you gave an answer too recently.")
+            }
+            AocClientError::AlreadySolved => write!(f, "this part is already solved."),
+            AocClientError::Offline => write!(f, "requests are disabled in offline mode."),
+            AocClientError::AocCli(e) => write!(f, "{e}"),
+            AocClientError::Request(e) => write!(f, "request failed: {e}"),
+        }
+    }
+}
+
+/// Print an error together with its suggested fix.
+pub fn print_error(context: &str, error: &AocClientError) {
+    eprintln!("{context}: {error}");
+    if let Some(suggestion) = error.suggestion() {
+        eprintln!("Hint: {suggestion}");
+    }
+}
+
+impl From<AocCommandError> for AocClientError {
+    /// Known errors are detected in the output of aoc-cli, other errors are passed on as-is.
+    fn from(value: AocCommandError) -> Self {
+        let known = match &value {
+            AocCommandError::BadExitStatus(output) => AocClientError::from_response(&format!(
+                "{}{}",
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            )),
+            _ => None,
+        };
+        known.unwrap_or(AocClientError::AocCli(value))
+    }
+}
+
+/// Pick the client to use.
+/// `AOC_CLIENT=aoc-cli` forces the aoc-cli wrapper, which is also used if no session cookie is configured but aoc-cli is installed.
+/// In offline mode, every request fails right away.
+pub fn client(year: Year) -> Box<dyn AocClient> {
+    if is_offline() {
+        return Box::new(OfflineClient);
+    }
+
+    let prefers_cli = env::var("AOC_CLIENT").is_ok_and(|c| c == "aoc-cli");
+
+    match profile::session() {
+        Some(session) if !prefers_cli => Box::new(NativeClient::new(base_url(), session, year)),
+        _ if prefers_cli || aoc_cli::check().is_ok() => Box::new(AocCli::new(year)),
+        _ => Box::new(NativeClient::new(base_url(), String::new(), year)),
+    }
+}
+
+/// Whether requests are disabled, with the global `--offline` flag or `AOC_OFFLINE=1`.
+pub fn is_offline() -> bool {
+    env::var("AOC_OFFLINE").is_ok_and(|v| !v.is_empty() && v != "0")
+}
+
+/// Client that is used in offline mode, it never touches the network.
+struct OfflineClient;
+
+impl AocClient for OfflineClient {
+    fn input(&self, _day: Day) -> Result<String, AocClientError> {
+        Err(AocClientError::Offline)
+    }
+
+    fn puzzle(&self, _day: Day) -> Result<String, AocClientError> {
+        Err(AocClientError::Offline)
+    }
+
+    fn submit(&self, _day: Day, _part: u8, _answer: &str) -> Result<String, AocClientError> {
+        Err(AocClientError::Offline)
+    }
+}
+
+/// Client that spaces out the requests of another client by at least `delay`, to follow the automation guidelines of the website.
+pub struct ThrottledClient {
+    inner: Box<dyn AocClient>,
+    delay: Duration,
+    last_request: Cell<Option<Instant>>,
+}
+
+impl ThrottledClient {
+    pub fn new(inner: Box<dyn AocClient>, delay: Duration) -> Self {
+        Self {
+            inner,
+            delay,
+            last_request: Cell::new(None),
+        }
+    }
+
+    fn throttle<T>(&self, request: impl FnOnce(&dyn AocClient) -> T) -> T {
+        if let Some(last_request) = self.last_request.get() {
+            thread::sleep(self.delay.saturating_sub(last_request.elapsed()));
+        }
+        let result = request(self.inner.as_ref());
+        self.last_request.set(Some(Instant::now()));
+        result
+    }
+}
+
+impl AocClient for ThrottledClient {
+    fn input(&self, day: Day) -> Result<String, AocClientError> {
+        self.throttle(|client| client.input(day))
+    }
+
+    fn puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        self.throttle(|client| client.puzzle(day))
+    }
+
+    fn submit(&self, day: Day, part: u8, answer: &str) -> Result<String, AocClientError> {
+        self.throttle(|client| client.submit(day, part, answer))
+    }
+}
+
+/// The base URL of the website, can be overridden with `AOC_BASE_URL` to test against a local server.
+fn base_url() -> String {
+    env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into())
+}
+
+/// Check a session cookie against the website, returns the name of the user it belongs to.
+pub fn check_session(session: String, year: Year) -> Result<String, AocClientError> {
+    if is_offline() {
+        return Err(AocClientError::Offline);
+    }
+    NativeClient::new(base_url(), session, year).user()
+}
+
+/// Client that sends requests to the website directly.
+pub struct NativeClient {
+    base_url: String,
+    session: String,
+    year: Year,
+    agent: ureq::Agent,
+}
+
+impl NativeClient {
+    pub fn new(base_url: String, session: String, year: Year) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .user_agent(USER_AGENT)
+            .build();
+
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            year,
+            agent,
+        }
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+
+    fn request(&self, method: &str, url: &str) -> Result<ureq::Request, AocClientError> {
+        if self.session.is_empty() {
+            return Err(AocClientError::SessionNotFound);
+        }
+
+        Ok(self
+            .agent
+            .request(method, url)
+            .set("Cookie", &format!("session={}", self.session)))
+    }
+
+    /// The name of the logged in user, shown on the calendar of the event. Used to check that a session is valid.
+    pub fn user(&self) -> Result<String, AocClientError> {
+        let url = format!("{}/{}", self.base_url, self.year);
+        let html = into_string(self.request("GET", &url)?.call())?;
+
+        let user = html
+            .split_once("<div class=\"user\">")
+            .map(|(_, rest)| rest.split(['<', '\n']).next().unwrap_or_default().trim())
+            .filter(|user| !user.is_empty())
+            .ok_or(AocClientError::SessionExpired)?;
+
+        Ok(html::decode_entities(user))
+    }
+}
+
+impl AocClient for NativeClient {
+    fn input(&self, day: Day) -> Result<String, AocClientError> {
+        let url = format!("{}/input", self.day_url(day));
+        into_string(self.request("GET", &url)?.call())
+    }
+
+    fn puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        let html = into_string(self.request("GET", &self.day_url(day))?.call())?;
+        Ok(html::to_markdown(&html))
+    }
+
+    fn submit(&self, day: Day, part: u8, answer: &str) -> Result<String, AocClientError> {
+        let url = format!("{}/answer", self.day_url(day));
+        let html = into_string(
+            self.request("POST", &url)?
+                .send_form(&[("level", &part.to_string()), ("answer", answer)]),
+        )?;
+
+        let response = html::to_markdown(&html);
+        match AocClientError::from_response(&response) {
+            Some(e) => Err(e),
+            None => Ok(response),
+        }
+    }
+}
+
+fn into_string(response: Result<ureq::Response, ureq::Error>) -> Result<String, AocClientError> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .map_err(|e| AocClientError::Request(e.to_string())),
+        Err(ureq::Error::Status(status, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            Err(
+                AocClientError::from_response(&body).unwrap_or(if status == 404 {
+                    AocClientError::NotUnlocked
+                } else {
+                    AocClientError::Request(format!("the server responded with status {status}."))
+                }),
+            )
+        }
+        Err(e) => Err(AocClientError::Request(e.to_string())),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    use std::time::{Duration, Instant};
+
+    use super::{AocClient, AocClientError, NativeClient, ThrottledClient};
+    use crate::{day, year};
+
+    /// Serve one canned response per request and return the requests that were received.
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let mut requests = vec![];
+
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                let mut content_length = 0;
+
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        content_length = len.trim().parse().unwrap();
+                    }
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    request.push_str(&line);
+                }
+
+                let mut content = vec![0; content_length];
+                reader.read_exact(&mut content).unwrap();
+                request.push_str(&String::from_utf8(content).unwrap());
+                requests.push(request);
+
+                write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 {status} OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+
+            requests
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn talks_to_server() {
+        let (base_url, handle) = serve(vec![
+            (200, "3   4\n4   3\n"),
+            (
+                200,
+                "<main><article><h2>--- Day 1 ---</h2><p>Hi</p></article></main>",
+            ),
+            (
+                200,
+                "<main><article><p>That's the right answer!</p></article></main>",
+            ),
+        ]);
+
+        let client = NativeClient::new(base_url, "abc".into(), year!(2024));
+        assert_eq!(client.input(day!(1)).unwrap(), "3   4\n4   3\n");
+        assert_eq!(client.puzzle(day!(1)).unwrap(), "## --- Day 1 ---\n\nHi\n");
+        assert_eq!(
+            client.submit(day!(1), 2, "42").unwrap(),
+            "That's the right answer!\n"
+        );
+
+        let requests = handle.join().unwrap();
+        assert!(requests[0].starts_with("GET /2024/day/1/input "));
+        assert!(requests[0].contains("session=abc"));
+        assert!(requests[1].starts_with("GET /2024/day/1 "));
+        assert!(requests[2].starts_with("POST /2024/day/1/answer "));
+        assert!(requests[2].ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn throttles_requests() {
+        let (base_url, server) = serve(vec![(200, "1\n"), (200, "2\n")]);
+        let client = ThrottledClient::new(
+            Box::new(NativeClient::new(base_url, "abc".into(), year!(2024))),
+            Duration::from_millis(200),
+        );
+
+        let start = Instant::now();
+        assert_eq!(client.input(day!(1)).unwrap(), "1\n");
+        assert_eq!(client.input(day!(2)).unwrap(), "2\n");
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        assert_eq!(server.join().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn reads_user() {
+        let (base_url, handle) = serve(vec![
+            (200, "<header><div class=\"user\">Tom &amp; Jerry <span class=\"star-count\">12*</span></div></header>"),
+            (200, "<header><div><a href=\"/2024/auth/login\">[Log In]</a></div></header>"),
+        ]);
+
+        let client = NativeClient::new(base_url, "abc".into(), year!(2024));
+        assert_eq!(client.user(), Ok("Tom & Jerry".into()));
+        assert_eq!(client.user(), Err(AocClientError::SessionExpired));
+
+        let requests = handle.join().unwrap();
+        assert!(requests[0].starts_with("GET /2024 "));
+        assert!(requests[0].contains("session=abc"));
+    }
+
+    #[test]
+    fn requires_session() {
+        let client = NativeClient::new("http://127.0.0.1:1".into(), String::new(), year!(2024));
+        assert!(matches!(
+            client.input(day!(1)),
+            Err(AocClientError::SessionNotFound)
+        ));
+    }
+
+    #[test]
+    fn detects_errors() {
+        let (base_url, handle) = serve(vec![
+            (400, "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"),
+            (404, "Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.\n"),
+            (200, "<main><article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 42s left to wait. <a href=\"/2024/day/1\">[Return to Day 1]</a></p></article></main>"),
+            (200, "<main><article><p>You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2024/day/1\">[Return to Day 1]</a></p></article></main>"),
+        ]);
+
+        let client = NativeClient::new(base_url, "abc".into(), year!(2024));
+        assert_eq!(client.input(day!(1)), Err(AocClientError::SessionExpired));
+        assert_eq!(client.input(day!(2)), Err(AocClientError::NotUnlocked));
+        assert_eq!(
+            client.submit(day!(1), 1, "42"),
+            Err(AocClientError::RateLimited {
+                wait: Some("42s".into())
+            })
+        );
+        assert_eq!(
+            client.submit(day!(1), 1, "42"),
+            Err(AocClientError::AlreadySolved)
+        );
+
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn suggests_fixes() {
+        assert_eq!(
+            AocClientError::RateLimited {
+                wait: Some("1m 2s".into())
+            }
+            .suggestion(),
+            Some("wait 1m 2s before submitting again.".into())
+        );
+        assert!(AocClientError::SessionExpired.suggestion().is_some());
+        assert_eq!(AocClientError::Request("timeout".into()).suggestion(), None);
+    }
+}
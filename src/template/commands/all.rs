@@ -1,5 +1,37 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::process;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{
+    all_days,
+    run_multi::{run_multi, RunOptions},
+    runner::{Limits, OutputFormat},
+    Year,
+};
+
+pub fn handle(
+    year: Year,
+    is_release: bool,
+    format: OutputFormat,
+    jobs: usize,
+    limits: Limits,
+    verify: bool,
+) {
+    let options = RunOptions {
+        year,
+        is_release,
+        count_allocs: false,
+        format,
+        jobs,
+        limits,
+        verify,
+    };
+
+    let summary = run_multi(&all_days(year).collect(), None, &options);
+
+    if summary.mismatches > 0 {
+        eprintln!(
+            "\n{} part(s) did not produce their known answer.",
+            summary.mismatches
+        );
+        process::exit(1);
+    }
 }
@@ -0,0 +1,20 @@
+use std::process;
+
+use crate::template::{answers::Answers, Day, Year};
+
+pub fn handle(year: Year, day: Day, part: u8, answer: &str) {
+    let mut answers = Answers::read_from_file(year);
+    let previous = answers.set(day, part, answer);
+
+    if let Err(e) = answers.store_file(year) {
+        eprintln!("Failed to store answer: {e}");
+        process::exit(1);
+    }
+
+    match previous {
+        Some(previous) if previous != answer => {
+            println!("Replaced answer to day {day}, part {part}: {previous} → {answer}");
+        }
+        _ => println!("Stored answer to day {day}, part {part}: {answer}"),
+    }
+}
@@ -1,14 +1,236 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::aoc_client::{self, AocClient, AocClientError, ThrottledClient};
+use crate::template::input_cache::{InputCache, InputState};
+use crate::template::puzzle::Puzzle;
+use crate::template::transaction::Transaction;
+use crate::template::{Day, Year, ANSI_BOLD, ANSI_RESET};
+use std::path::Path;
+use std::time::Duration;
+use std::{fs, io, process};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+/// Outcome of downloading a day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Downloaded,
+    /// Input and puzzle were already downloaded.
+    Present,
+}
+
+/// Why downloading a day failed.
+#[derive(Debug)]
+pub enum DownloadError {
+    Client(AocClientError),
+    Write { path: String, error: io::Error },
+}
+
+impl DownloadError {
+    /// Print the error, together with a suggested fix if there is one.
+    pub fn print(&self, day: Day) {
+        let context = format!("Failed to download day {day}");
+        match self {
+            DownloadError::Client(e) => aoc_client::print_error(&context, e),
+            DownloadError::Write { path, error } => {
+                eprintln!("{context}: could not write \"{path}\": {error}");
+            }
+        }
+    }
+}
+
+impl From<AocClientError> for DownloadError {
+    fn from(value: AocClientError) -> Self {
+        DownloadError::Client(value)
+    }
+}
+
+/// Download the input and puzzle of a day.
+pub fn handle(year: Year, day: Day, force: bool) {
+    let client = aoc_client::client(year);
+    let mut transaction = Transaction::default();
+
+    if let Err(e) = download(client.as_ref(), year, day, force, true, &mut transaction) {
+        e.print(day);
+        transaction.rollback();
         process::exit(1);
     }
+}
+
+/// Download several days, spacing out requests by `delay`.
+/// Days whose input and puzzle are already downloaded or that are still locked are skipped, a summary is printed at the end.
+/// Unlike a single download, a stored puzzle is not fetched again to look for part two, unless `force` is set.
+pub fn handle_many(year: Year, days: &[Day], force: bool, delay: Duration) {
+    let client = ThrottledClient::new(aoc_client::client(year), delay);
+
+    let mut downloaded = vec![];
+    let mut present = vec![];
+    let mut locked = vec![];
+    let mut failed = vec![];
+    let mut skipped = vec![];
+
+    for (index, &day) in days.iter().enumerate() {
+        if !year.is_unlocked(day) {
+            locked.push(day);
+            continue;
+        }
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+
+        let mut transaction = Transaction::default();
+
+        match download(&client, year, day, force, false, &mut transaction) {
+            Ok(Outcome::Downloaded) => downloaded.push(day),
+            Ok(Outcome::Present) => present.push(day),
+            Err(DownloadError::Client(AocClientError::NotUnlocked)) => {
+                println!("Not unlocked yet.");
+                locked.push(day);
+            }
+            Err(e) => {
+                e.print(day);
+                transaction.rollback();
+                failed.push(day);
+
+                // NOTE: these errors apply to every request, there is no point in trying the remaining days.
+                if matches!(
+                    e,
+                    DownloadError::Client(
+                        AocClientError::Offline
+                            | AocClientError::SessionNotFound
+                            | AocClientError::SessionExpired
+                    )
+                ) {
+                    skipped.extend_from_slice(&days[index + 1..]);
+                    break;
+                }
+            }
+        }
+        println!();
+    }
+
+    println!("{ANSI_BOLD}Summary{ANSI_RESET}");
+    println!("------");
+    for (label, days) in [
+        ("Downloaded", &downloaded),
+        ("Already present", &present),
+        ("Locked", &locked),
+        ("Failed", &failed),
+        ("Skipped", &skipped),
+    ] {
+        if !days.is_empty() {
+            let list = days.iter().map(Day::to_string).collect::<Vec<_>>();
+            println!("{label}: {} ({})", days.len(), list.join(", "));
+        }
+    }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if !failed.is_empty() {
         process::exit(1);
+    }
+}
+
+/// Download the input and puzzle of a day, writing the files as part of `transaction`.
+/// A downloaded input is only fetched again with `force`. With `refresh_puzzle`, a stored puzzle is fetched again until it contains part two.
+pub fn download(
+    client: &dyn AocClient,
+    year: Year,
+    day: Day,
+    force: bool,
+    refresh_puzzle: bool,
+    transaction: &mut Transaction,
+) -> Result<Outcome, DownloadError> {
+    let mut cache = InputCache::read_from_file(year);
+    let input_path = InputCache::input_path(year, day);
+    let puzzle_path = Puzzle::path(year, day);
+
+    let fetch_input = force || cache.state(year, day) == InputState::Missing;
+    let fetch_puzzle = force
+        || match Puzzle::read(year, day) {
+            None => true,
+            Some(puzzle) => refresh_puzzle && puzzle.part(2).is_none(),
+        };
+
+    if !fetch_input {
+        println!("Kept input \"{input_path}\", it was already downloaded. Pass `--force` to download it again.");
+    }
+
+    if !fetch_input && !fetch_puzzle {
+        return Ok(Outcome::Present);
+    }
+
+    let input = fetch_input.then(|| client.input(day)).transpose()?;
+    let puzzle = fetch_puzzle.then(|| client.puzzle(day)).transpose()?;
+
+    if let Some(input) = input {
+        write_file(transaction, &input_path, &input)?;
+        cache.record(day, &input);
+        if let Err(e) = cache.store_file(year, transaction) {
+            eprintln!("Failed to store checksum of the input: {e}");
+        }
+        println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    }
+
+    if let Some(puzzle) = puzzle {
+        write_file(transaction, &puzzle_path, &puzzle)?;
+        println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+        write_examples(transaction, year, day, &Puzzle::parse(&puzzle))?;
+    }
+
+    Ok(Outcome::Downloaded)
+}
+
+fn write_file(
+    transaction: &mut Transaction,
+    path: &str,
+    contents: &str,
+) -> Result<(), DownloadError> {
+    transaction
+        .write(Path::new(path), contents.as_bytes(), true)
+        .map_err(|error| DownloadError::Write {
+            path: path.into(),
+            error,
+        })
+}
+
+/// Write the examples of the puzzle to the example files. Parts with different examples get separate `-1` / `-2` files.
+/// Example files that already have contents are left untouched.
+fn write_examples(
+    transaction: &mut Transaction,
+    year: Year,
+    day: Day,
+    puzzle: &Puzzle,
+) -> Result<(), DownloadError> {
+    let part_one = puzzle.example(1);
+    let part_two = puzzle.example(2);
+
+    let files = match (part_one, part_two) {
+        (Some(one), Some(two)) if one != two => vec![
+            (year.data_path(&format!("examples/{day}-1.txt")), one),
+            (year.data_path(&format!("examples/{day}-2.txt")), two),
+        ],
+        (Some(one), _) => vec![(year.data_path(&format!("examples/{day}.txt")), one)],
+        (None, Some(two)) => vec![(year.data_path(&format!("examples/{day}-2.txt")), two)],
+        (None, None) => {
+            println!("No example found in the puzzle description.");
+            return Ok(());
+        }
     };
+
+    for (path, example) in files {
+        match fs::read_to_string(&path) {
+            Ok(existing) if existing == example => {}
+            Ok(existing) if !existing.trim().is_empty() => {
+                println!("Kept example file \"{path}\", it already has contents.");
+            }
+            _ => {
+                write_file(transaction, &path, &example)?;
+                println!("🎄 Successfully wrote example to \"{path}\".");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Create the directory of a file, e.g. `data/2024/inputs/` for the first download of a year.
+pub fn create_parent(path: &str) -> io::Result<()> {
+    match Path::new(path).parent() {
+        Some(parent) => fs::create_dir_all(parent),
+        None => Ok(()),
+    }
 }
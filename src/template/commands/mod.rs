@@ -1,6 +1,8 @@
 pub mod all;
+pub mod answer;
 pub mod download;
 pub mod read;
 pub mod scaffold;
+pub mod session;
 pub mod solve;
 pub mod time;
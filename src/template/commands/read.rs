@@ -1,15 +1,43 @@
-use std::process;
+use std::io::{stdout, IsTerminal};
+use std::{fs, process};
 
-use crate::template::{aoc_cli, Day};
+use crate::template::commands::download::create_parent;
+use crate::template::puzzle::Puzzle;
+use crate::template::{aoc_client, markdown, Day, Year};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+/// Print the puzzle description. The stored description is used, unless it is missing or does not contain part two yet.
+pub fn handle(year: Year, day: Day) {
+    let puzzle_path = Puzzle::path(year, day);
+    let cached = fs::read_to_string(&puzzle_path).ok();
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    let puzzle = match cached {
+        Some(puzzle) if Puzzle::parse(&puzzle).part(2).is_some() => puzzle,
+        // NOTE: in offline mode, part two can not be fetched anyway.
+        Some(puzzle) if aoc_client::is_offline() => puzzle,
+        cached => match aoc_client::client(year).puzzle(day) {
+            Ok(puzzle) => {
+                if let Err(e) =
+                    create_parent(&puzzle_path).and_then(|()| fs::write(&puzzle_path, &puzzle))
+                {
+                    eprintln!("Failed to write \"{puzzle_path}\": {e}");
+                }
+                puzzle
+            }
+            Err(e) => {
+                aoc_client::print_error(&format!("Failed to read day {day}"), &e);
+                match cached {
+                    Some(puzzle) => {
+                        eprintln!("Showing the stored description from \"{puzzle_path}\".\n");
+                        puzzle
+                    }
+                    None => process::exit(1),
+                }
+            }
+        },
     };
+
+    print!(
+        "{}",
+        markdown::render(&puzzle, markdown::terminal_width(), stdout().is_terminal())
+    );
 }
@@ -1,79 +1,247 @@
-use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
-};
+use std::{fs, path::Path, process};
 
-use crate::template::Day;
+use crate::template::commands::download;
+use crate::template::input_cache::InputCache;
+use crate::template::puzzle::Puzzle;
+use crate::template::transaction::Transaction;
+use crate::template::{aoc_client, Day, Year};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
-    let mut file = OpenOptions::new();
-    if overwrite {
-        file.create(true);
-    } else {
-        file.create_new(true);
-    }
-    file.truncate(true).write(true).open(path)
+/// What scaffolding does with a file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Action {
+    Create,
+    Overwrite,
+    /// The file exists and is left untouched.
+    Keep,
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// A file that is written by scaffolding a day.
+struct Step {
+    name: &'static str,
+    path: String,
+    contents: String,
+    action: Action,
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Plan the files of a day. Existing inputs and examples are always kept, an existing module is only replaced with `overwrite`.
+fn plan(year: Year, day: Day, overwrite: bool) -> Result<Vec<Step>, String> {
+    let module_path = year.bin_path(day);
+    let module_action = match (Path::new(&module_path).exists(), overwrite) {
+        (false, _) => Action::Create,
+        (true, true) => Action::Overwrite,
+        (true, false) => {
+            return Err(format!(
+                "Module file \"{module_path}\" already exists. Pass `--overwrite` to replace it."
+            ))
+        }
+    };
+
+    let mut steps = vec![Step {
+        name: "module file",
+        path: module_path,
+        contents: MODULE_TEMPLATE.replace("%DAY_NUMBER%", &day.into_inner().to_string()),
+        action: module_action,
+    }];
+
+    let inputs = [
+        ("input file", InputCache::input_path(year, day)),
+        (
+            "example file",
+            year.data_path(&format!("examples/{day}.txt")),
+        ),
+    ];
+    for (name, path) in inputs {
+        let action = if Path::new(&path).exists() {
+            Action::Keep
+        } else {
+            Action::Create
+        };
+        steps.push(Step {
+            name,
+            path,
+            contents: String::new(),
+            action,
+        });
+    }
+
+    Ok(steps)
+}
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
+/// Scaffold a day. Existing inputs and examples are never touched, the module is only replaced with `overwrite`.
+/// With `download`, the input and puzzle are downloaded as part of the scaffold, the tests are then filled in with the example answers.
+/// If a step fails, the files written so far are removed again. With `dry_run`, the planned changes are only printed.
+pub fn handle(year: Year, day: Day, overwrite: bool, download: bool, dry_run: bool) {
+    let steps = match plan(year, day, overwrite) {
+        Ok(steps) => steps,
         Err(e) => {
-            eprintln!("Failed to create module file: {e}");
+            eprintln!("{e}");
             process::exit(1);
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+    let refresh = download || Puzzle::read(year, day).is_some();
+
+    if dry_run {
+        for step in &steps {
+            let action = match step.action {
+                Action::Create => "Would create",
+                Action::Overwrite => "Would overwrite",
+                Action::Keep => "Would keep existing",
+            };
+            println!("{action} {} \"{}\"", step.name, step.path);
         }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+        if download {
+            println!("Would download the input and puzzle of day {day}.");
+        }
+        if refresh {
+            println!("Would update the tests with the example answers of the puzzle.");
         }
+        return;
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+    let mut transaction = Transaction::default();
+
+    for step in &steps {
+        let path = Path::new(&step.path);
+        let empty = if step.contents.is_empty() {
+            "empty "
+        } else {
+            ""
+        };
+
+        match step.action {
+            Action::Keep => println!("Kept existing {} \"{}\"", step.name, step.path),
+            action => {
+                match transaction.write(path, step.contents.as_bytes(), action == Action::Overwrite)
+                {
+                    Ok(()) if action == Action::Overwrite => {
+                        println!("Overwrote {} \"{}\"", step.name, step.path);
+                    }
+                    Ok(()) => println!("Created {empty}{} \"{}\"", step.name, step.path),
+                    Err(e) => {
+                        eprintln!("Failed to write {} \"{}\": {e}", step.name, step.path);
+                        transaction.rollback();
+                        process::exit(1);
+                    }
+                }
+            }
         }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
+    }
+
+    if download {
+        let client = aoc_client::client(year);
+        if let Err(e) =
+            download::download(client.as_ref(), year, day, false, true, &mut transaction)
+        {
+            e.print(day);
+            transaction.rollback();
             process::exit(1);
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
+    if refresh {
+        if let Err(e) = try_refresh_tests(year, day, &mut transaction) {
+            eprintln!("{e}");
+            transaction.rollback();
             process::exit(1);
         }
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    println!("🎄 Type `cargo solve {day} --year {year}` to run your solution.");
+}
+
+/// Update the tests of a solution with the example answers found in the puzzle description.
+/// Only the example file and the expected value of `test_part_one` / `test_part_two` are changed.
+pub fn refresh_tests(year: Year, day: Day) {
+    if let Err(e) = try_refresh_tests(year, day, &mut Transaction::default()) {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+}
+
+fn try_refresh_tests(year: Year, day: Day, transaction: &mut Transaction) -> Result<(), String> {
+    let module_path = year.bin_path(day);
+
+    let Some(puzzle) = Puzzle::read(year, day) else {
+        return Err(format!(
+            "No puzzle description found in \"{}\". Run `cargo download {day} --year {year}` first.",
+            Puzzle::path(year, day)
+        ));
+    };
+
+    let mut module =
+        fs::read_to_string(&module_path).map_err(|e| format!("Failed to read module file: {e}"))?;
+
+    for (part, test) in [(1, "test_part_one"), (2, "test_part_two")] {
+        let Some(answer) = puzzle.example_answer(part) else {
+            continue;
+        };
+
+        let split_example =
+            Path::new(&year.data_path(&format!("examples/{day}-{part}.txt"))).exists();
+
+        match update_test(&module, test, &answer, split_example.then_some(part)) {
+            Some(updated) if updated != module => {
+                module = updated;
+                println!("Updated `{test}` to expect {answer}.");
+            }
+            Some(_) => {}
+            None => println!("Skipped `{test}`, expected a numeric answer or an `assert_eq!(result, ..)`, found \"{answer}\"."),
+        }
+    }
+
+    transaction
+        .write(Path::new(&module_path), module.as_bytes(), true)
+        .map_err(|e| format!("Failed to write module file: {e}"))
+}
+
+/// Replace the expected value of a test. If `part` is set, the test reads the example file of that part.
+fn update_test(module: &str, test: &str, answer: &str, part: Option<u8>) -> Option<String> {
+    answer.parse::<u64>().ok()?;
+
+    let start = module.find(&format!("fn {test}()"))?;
+    let assert_start = start + module[start..].find("assert_eq!(result, ")?;
+    let assert_end = assert_start + module[assert_start..].find(");")? + 2;
+
+    let mut body = module[start..assert_start].to_string();
+    if let Some(part) = part {
+        body = body.replace(
+            "read_file(\"examples\", DAY)",
+            &format!("read_file_part(\"examples\", DAY, {part})"),
+        );
+    }
+
+    Some(format!(
+        "{}{body}assert_eq!(result, Some({answer}));{}",
+        &module[..start],
+        &module[assert_end..]
+    ))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{update_test, MODULE_TEMPLATE};
+
+    #[test]
+    fn updates_tests() {
+        let updated = update_test(MODULE_TEMPLATE, "test_part_two", "42", Some(2)).unwrap();
+        assert!(updated.contains("assert_eq!(result, Some(42));"));
+        assert!(updated
+            .contains("part_two(&advent_of_code::template::read_file_part(\"examples\", DAY, 2))"));
+        assert_eq!(updated.matches("assert_eq!(result, None);").count(), 1);
+        assert_eq!(updated.matches("read_file(\"examples\", DAY)").count(), 1);
+
+        let refreshed = update_test(&updated, "test_part_two", "43", None).unwrap();
+        assert!(refreshed.contains("assert_eq!(result, Some(43));"));
+
+        assert_eq!(
+            update_test(MODULE_TEMPLATE, "test_part_one", "abc", None),
+            None
+        );
+        assert_eq!(update_test("", "test_part_one", "42", None), None);
+    }
 }
@@ -0,0 +1,97 @@
+use std::io::{self, BufRead, IsTerminal};
+use std::process;
+
+use crate::template::aoc_client::{self, check_session, AocClientError};
+use crate::template::profile::{self, Profile};
+use crate::template::Year;
+
+/// Store the session cookie of the selected profile, or of the default session. The cookie is read from stdin and checked before it is stored.
+pub fn handle(year: Year) {
+    let profile = Profile::active();
+
+    if io::stdin().is_terminal() {
+        match &profile {
+            Some(profile) => eprint!("Paste the `session` cookie for profile \"{profile}\": "),
+            None => eprint!("Paste the `session` cookie: "),
+        }
+    }
+
+    let mut session = String::new();
+    if let Err(e) = io::stdin().lock().read_line(&mut session) {
+        eprintln!("Failed to read the session cookie: {e}");
+        process::exit(1);
+    }
+
+    let session = session.trim();
+    if session.is_empty() {
+        eprintln!("No session cookie given.");
+        process::exit(1);
+    }
+
+    match check_session(session.to_string(), year) {
+        Ok(user) => println!("Session belongs to {user}."),
+        Err(AocClientError::Offline) => {
+            println!("Offline, storing the session without checking it.");
+        }
+        Err(e) => {
+            aoc_client::print_error("Failed to check the session", &e);
+            process::exit(1);
+        }
+    }
+
+    let stored = match &profile {
+        Some(profile) => profile.store_session(session),
+        None => profile::default_session_path()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no home directory found"))
+            .and_then(|path| profile::write_private(&path, session).map(|()| path)),
+    };
+
+    match stored {
+        Ok(path) => println!("Stored the session in \"{}\".", path.display()),
+        Err(e) => {
+            eprintln!("Failed to store the session: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Check that the session of the selected profile, or the default session, is still valid.
+pub fn handle_check(year: Year) {
+    let label = Profile::active().map_or("default session".into(), |p| format!("profile \"{p}\""));
+
+    let Some(session) = profile::session() else {
+        aoc_client::print_error(
+            &format!("Failed to check the {label}"),
+            &AocClientError::SessionNotFound,
+        );
+        process::exit(1);
+    };
+
+    match check_session(session, year) {
+        Ok(user) => println!("The {label} is valid, logged in as {user}."),
+        Err(e) => {
+            aoc_client::print_error(&format!("Failed to check the {label}"), &e);
+            process::exit(1);
+        }
+    }
+}
+
+/// List the profiles that have a stored session.
+pub fn handle_list() {
+    let profiles = Profile::list();
+
+    if profiles.is_empty() {
+        println!("No profiles yet, add one with `cargo session --profile <name>`.");
+        return;
+    }
+
+    let active = Profile::active();
+    for profile in profiles {
+        let marker = if active.as_ref() == Some(&profile) {
+            " (selected)"
+        } else {
+            ""
+        };
+        println!("{profile}{marker}");
+    }
+}
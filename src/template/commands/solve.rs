@@ -1,9 +1,29 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{
+    input_cache::{InputCache, InputState},
+    profile::Profile,
+    run_multi::{run_inputs, RunOptions},
+    runner::{BenchConfig, Limits, OutputFormat},
+    Day, Year,
+};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Year,
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    yes: bool,
+    bench_config: Option<&BenchConfig>,
+    count_allocs: bool,
+    spans: bool,
+    format: OutputFormat,
+) {
+    check_input(year, day);
+
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), year.bin_name(day)];
 
     if dhat {
         cmd_args.extend([
@@ -16,11 +36,30 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push("--release".to_string());
     }
 
+    if count_allocs {
+        cmd_args.extend(["--features".to_string(), "count-allocs".to_string()]);
+    }
+
+    if spans {
+        cmd_args.extend(["--features".to_string(), "spans".to_string()]);
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
+        if yes {
+            cmd_args.push("--yes".to_string());
+        }
+    }
+
+    if format != OutputFormat::Human {
+        cmd_args.extend(["--format".to_string(), format.to_string()]);
+    }
+
+    if let Some(bench_config) = bench_config {
+        cmd_args.extend(bench_config.to_args());
     }
 
     let mut cmd = Command::new("cargo")
@@ -32,3 +71,56 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd.wait().unwrap();
 }
+
+/// Warn if the input is missing or was edited since it was downloaded.
+fn check_input(year: Year, day: Day) {
+    let path = InputCache::input_path(year, day);
+    let profile = Profile::active().map_or(String::new(), |p| format!(" --profile {p}"));
+
+    match InputCache::read_from_file(year).state(year, day) {
+        InputState::Missing => {
+            eprintln!("Warning: the input \"{path}\" is missing or empty. Run `cargo download {day} --year {year}{profile}` to download it.");
+        }
+        InputState::Changed => {
+            eprintln!("Warning: the input \"{path}\" changed since it was downloaded. Run `cargo download {day} --year {year}{profile} --force` to restore it.");
+        }
+        InputState::Unknown | InputState::Unchanged => {}
+    }
+}
+
+/// Run the solution against every input of the day and verify the answers of each input.
+pub fn handle_all_inputs(year: Year, day: Day, is_release: bool, count_allocs: bool) {
+    let inputs = InputCache::inputs(year, day);
+
+    if inputs.is_empty() {
+        eprintln!(
+            "No inputs found for day {day}, expected \"{}\" or files in \"{}\".",
+            year.data_path(&format!("inputs/{day}.txt")),
+            year.data_path(&format!("inputs/{day}/"))
+        );
+        process::exit(1);
+    }
+
+    let options = RunOptions {
+        year,
+        is_release,
+        count_allocs,
+        format: OutputFormat::Human,
+        jobs: 1,
+        limits: Limits::default(),
+        verify: true,
+    };
+
+    let Ok(summary) = run_inputs(day, &inputs, &options) else {
+        eprintln!("Could not build the solution of day {day}.");
+        process::exit(1);
+    };
+
+    if summary.mismatches > 0 {
+        eprintln!(
+            "\n{} part(s) did not produce their known answer.",
+            summary.mismatches
+        );
+        process::exit(1);
+    }
+}
@@ -1,19 +1,37 @@
 use std::collections::HashSet;
+use std::process;
+use std::time::Duration;
 
-use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::history;
+use crate::template::run_multi::{run_multi, RunOptions};
+use crate::template::runner::{BenchConfig, Limits, OutputFormat};
+use crate::template::timings::{Delta, Timings};
+use crate::template::{all_days, readme_benchmarks, Day, Year, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Year,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    bench_config: &BenchConfig,
+    compare_threshold: Option<f64>,
+    count_allocs: bool,
+    format: OutputFormat,
+    limits: &Limits,
+) {
+    let stored_timings = Timings::read_from_file(year);
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
-                all_days().collect()
+                all_days(year).collect()
+            } else if compare_threshold.is_some() {
+                // when comparing, re-run the days that have stored timings.
+                stored_timings.data.iter().map(|t| t.day).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
+                all_days(year)
                     .filter(|day| !stored_timings.is_day_complete(*day))
                     .collect()
             }
@@ -21,20 +39,132 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let options = RunOptions {
+        year,
+        is_release: true,
+        count_allocs,
+        format,
+        jobs: 1,
+        limits: limits.clone(),
+        verify: false,
+    };
+
+    let timings = run_multi(&days_to_run, Some(bench_config), &options)
+        .timings
+        .unwrap();
+
+    let regressions = compare_threshold.map(|threshold| {
+        let deltas = stored_timings.compare(&timings);
+        let report = format_deltas(&deltas, threshold);
+        match format {
+            OutputFormat::Human => print!("{report}"),
+            OutputFormat::Json => eprint!("{report}"),
+        }
+        deltas.iter().filter(|d| d.is_regression(threshold)).count()
+    });
 
     if store {
+        if let Err(e) = history::append(year, &history::Entry::new(timings.clone())) {
+            eprintln!("Failed to append to benchmark history: {e}");
+        }
+
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year).unwrap();
 
-        println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update() {
+            // keep stdout reserved for records in json mode.
+            Ok(()) if format == OutputFormat::Json => {
+                eprintln!("Stored updated benchmarks.");
+            }
             Ok(()) => {
-                println!("Stored updated benchmarks.");
+                println!("\nStored updated benchmarks.");
             }
             Err(_) => {
                 eprintln!("Failed to store updated benchmarks.");
             }
         }
     }
+
+    if let (Some(regressions), Some(threshold)) = (regressions, compare_threshold) {
+        if regressions > 0 {
+            eprintln!("\n{regressions} part(s) regressed by more than {threshold}%.");
+            process::exit(1);
+        }
+    }
+}
+
+/// Print the stored history of timings for a single day, oldest first.
+pub fn handle_history(year: Year, day: Day) {
+    let entries = history::read(year);
+
+    println!("{ANSI_BOLD}Day {day} history ({year}){ANSI_RESET}");
+    println!("------");
+
+    let mut has_entries = false;
+
+    for entry in &entries {
+        let Some(timing) = entry.get(day) else {
+            continue;
+        };
+        has_entries = true;
+
+        let revision = entry
+            .revision
+            .as_ref()
+            .map_or("unknown", |r| &r[..r.len().min(7)]);
+        let dirty = if entry.dirty { "-dirty" } else { "" };
+
+        let parts = [
+            ("Parse", timing.parse_nanos()),
+            ("Part 1", timing.part_1_nanos()),
+            ("Part 2", timing.part_2_nanos()),
+        ]
+        .iter()
+        .filter_map(|(part, nanos)| Some(format!("{part}: {:>9}", format_nanos((*nanos)?))))
+        .collect::<Vec<_>>()
+        .join("  ");
+
+        println!(
+            "{} {:<13} {parts}",
+            entry.date,
+            format!("{revision}{dirty}")
+        );
+    }
+
+    if !has_entries {
+        println!("No history stored. Run `cargo time {day} --year {year} --store` to record one.");
+    }
+}
+
+fn format_deltas(deltas: &[Delta], threshold: f64) -> String {
+    let mut out = format!("\n{ANSI_BOLD}Comparison with stored timings{ANSI_RESET}\n------\n");
+
+    if deltas.is_empty() {
+        out.push_str("No stored timings to compare with.\n");
+        return out;
+    }
+
+    for delta in deltas {
+        let marker = if delta.is_regression(threshold) {
+            "✖"
+        } else {
+            "✓"
+        };
+
+        out.push_str(&format!(
+            "Day {} {:<6} {:>10} → {:>10} {:>+8.1}% {marker}\n",
+            delta.day,
+            delta.part,
+            format_nanos(delta.old_nanos),
+            format_nanos(delta.new_nanos),
+            delta.percent(),
+        ));
+    }
+
+    out
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos as u64))
 }
@@ -5,10 +5,18 @@ use std::str::FromStr;
 #[cfg(feature = "today")]
 use chrono::{Datelike, FixedOffset, Utc};
 
+use crate::template::event::{days_in_event, FIRST_YEAR};
+use crate::template::Year;
+
+/// The last day of the longest event, the first events had the most days.
+const MAX_DAY: u8 = days_in_event(FIRST_YEAR);
+
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
 
-/// A valid day number of advent (i.e. an integer in range 1 to 25).
+/// A valid day number of advent (i.e. an integer in range 1 to the last day of the longest event).
+///
+/// Events differ in length, use [`Day::new_in`] or [`Year::has_day`] to check if a day is part of a specific event.
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -24,13 +32,34 @@ pub struct Day(u8);
 impl Day {
     /// Creates a [`Day`] from the provided value if it's in the valid range,
     /// returns [`None`] otherwise.
-    pub fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+    pub const fn new(day: u8) -> Option<Self> {
+        if day == 0 || day > MAX_DAY {
             return None;
         }
         Some(Self(day))
     }
 
+    /// Creates a [`Day`] from the provided value if it's a day of the event of `year`,
+    /// returns [`None`] otherwise.
+    ///
+    /// ```
+    /// # use advent_of_code::template::{Day, Year};
+    /// let year = Year::new(2025).unwrap();
+    /// assert!(Day::new_in(year, 12).is_some());
+    /// assert!(Day::new_in(year, 13).is_none());
+    /// ```
+    pub fn new_in(year: Year, day: u8) -> Option<Self> {
+        Self::new(day).filter(|day| year.has_day(*day))
+    }
+
+    /// Parses a [`Day`] that is a day of the event of `year`, the error names the last day of that event.
+    pub fn parse_in(year: Year, s: &str) -> Result<Self, DayFromStrError> {
+        s.parse()
+            .ok()
+            .and_then(|day| Self::new_in(year, day))
+            .ok_or(DayFromStrError { year: Some(year) })
+    }
+
     // Not part of the public API
     #[doc(hidden)]
     pub const fn __new_unchecked(day: u8) -> Self {
@@ -38,19 +67,20 @@ impl Day {
     }
 
     /// Converts the [`Day`] into an [`u8`].
-    pub fn into_inner(self) -> u8 {
+    pub const fn into_inner(self) -> u8 {
         self.0
     }
 }
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if it's a day of this year's event, `None` otherwise.
     pub fn today() -> Option<Self> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
         let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
-            Self::new(u8::try_from(today.day()).ok()?)
+        if today.month() == 12 {
+            let year = Year::new(u16::try_from(today.year()).ok()?)?;
+            Self::new_in(year, u8::try_from(today.day()).ok()?)
         } else {
             None
         }
@@ -81,39 +111,53 @@ impl FromStr for Day {
     type Err = DayFromStrError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let day = s.parse().map_err(|_| DayFromStrError)?;
-        Self::new(day).ok_or(DayFromStrError)
+        let error = DayFromStrError { year: None };
+        let day = s.parse().map_err(|_| error)?;
+        Self::new(day).ok_or(error)
     }
 }
 
 /// An error which can be returned when parsing a [`Day`].
-#[derive(Debug)]
-pub struct DayFromStrError;
+#[derive(Debug, Clone, Copy)]
+pub struct DayFromStrError {
+    /// The year whose event the day was checked against, see [`Day::parse_in`].
+    year: Option<Year>,
+}
 
 impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        match self.year {
+            Some(year) => write!(
+                f,
+                "expecting a day number between 1 and {}, the last day of the {year} event",
+                year.days()
+            ),
+            None => write!(f, "expecting a day number between 1 and {MAX_DAY}"),
+        }
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
-pub fn all_days() -> AllDays {
-    AllDays::new()
+/// An iterator that yields every day of the event of `year`, from the 1st to the last day.
+pub fn all_days(year: Year) -> AllDays {
+    AllDays::new(year)
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of the event of a year, from the 1st to the last day.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
-    #[allow(clippy::new_without_default)]
-    pub fn new() -> Self {
-        Self { current: 1 }
+    pub fn new(year: Year) -> Self {
+        Self {
+            current: 1,
+            last: year.days(),
+        }
     }
 }
 
@@ -121,10 +165,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above the last day of the event.
         let day = Day(self.current);
         self.current += 1;
 
@@ -135,15 +179,27 @@ impl Iterator for AllDays {
 /* -------------------------------------------------------------------------- */
 
 /// Creates a [`Day`] value in a const context.
+///
+/// `day!(n)` alone only checks the day against the longest event, i.e. that it is between 1 and 25.
+/// Pass a year as second argument to check the day against the length of that year's event, e.g. `day!(12, 2025)`.
 #[macro_export]
 macro_rules! day {
     ($day:expr) => {{
         const _ASSERT: () = assert!(
-            $day != 0 && $day <= 25,
+            $crate::template::Day::new($day).is_some(),
+            concat!("invalid day number `", $day, "`, no event has this day"),
+        );
+        $crate::template::Day::__new_unchecked($day)
+    }};
+    ($day:expr, $year:expr) => {{
+        const _ASSERT: () = assert!(
+            $day != 0 && $day <= $crate::year!($year).days(),
             concat!(
                 "invalid day number `",
                 $day,
-                "`, expecting a value between 1 and 25"
+                "`, the event of ",
+                $year,
+                " does not have this day"
             ),
         );
         $crate::template::Day::__new_unchecked($day)
@@ -155,10 +211,11 @@ macro_rules! day {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{all_days, Day};
+    use crate::year;
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
+        let mut iter = all_days(year!(2024));
 
         assert_eq!(iter.next(), Some(Day(1)));
         assert_eq!(iter.next(), Some(Day(2)));
@@ -187,6 +244,29 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn shorter_events() {
+        assert_eq!(all_days(year!(2025)).last(), Some(Day(12)));
+        assert_eq!(all_days(year!(2025)).count(), 12);
+        assert_eq!(Day::new_in(year!(2025), 12), Some(Day(12)));
+        assert_eq!(Day::new_in(year!(2025), 13), None);
+        assert_eq!(Day::new_in(year!(2024), 25), Some(Day(25)));
+        assert_eq!(crate::day!(12, 2025), Day(12));
+    }
+
+    #[test]
+    fn names_the_last_day_in_errors() {
+        assert_eq!(Day::parse_in(year!(2025), "12").unwrap(), Day(12));
+        assert_eq!(
+            Day::parse_in(year!(2025), "13").unwrap_err().to_string(),
+            "expecting a day number between 1 and 12, the last day of the 2025 event"
+        );
+        assert_eq!(
+            "26".parse::<Day>().unwrap_err().to_string(),
+            "expecting a day number between 1 and 25"
+        );
+    }
 }
 
 /* -------------------------------------------------------------------------- */
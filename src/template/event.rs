@@ -0,0 +1,14 @@
+//! The length of each event. This file is also included by `build.rs` to filter the solutions of the registry,
+//! so it must not depend on the rest of the crate.
+
+/// The year of the first event.
+pub const FIRST_YEAR: u16 = 2015;
+
+/// The number of days of the event of a year. Events up to 2024 have 25 days, later events have 12.
+pub const fn days_in_event(year: u16) -> u8 {
+    if year >= 2025 {
+        12
+    } else {
+        25
+    }
+}
@@ -0,0 +1,230 @@
+/// Module that keeps an append-only log of stored benchmark timings.
+/// Every entry records the timings of a `cargo time --store` run together with the git revision it was taken at.
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::process::Command;
+use std::str::FromStr;
+
+use tinyjson::JsonValue;
+
+use crate::template::date;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{Day, Year};
+
+/// Timings of a single `cargo time --store` run.
+#[derive(Clone, Debug)]
+pub struct Entry {
+    pub revision: Option<String>,
+    pub dirty: bool,
+    pub date: String,
+    pub timings: Timings,
+}
+
+impl Entry {
+    /// Create an entry for the current point in time and git revision.
+    pub fn new(timings: Timings) -> Self {
+        Self {
+            revision: git_revision(),
+            dirty: is_git_dirty(),
+            date: date::format_date(date::now()),
+            timings,
+        }
+    }
+
+    /// The timing of a day in this entry, if it was run.
+    pub fn get(&self, day: Day) -> Option<&Timing> {
+        self.timings.data.iter().find(|t| t.day == day)
+    }
+}
+
+/// The history file of a year.
+fn path(year: Year) -> String {
+    year.data_path("timings-history.jsonl")
+}
+
+/// Append an entry to the history file.
+pub fn append(year: Year, entry: &Entry) -> Result<(), io::Error> {
+    let json = JsonValue::from(entry)
+        .stringify()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path(year))?;
+
+    writeln!(file, "{json}")
+}
+
+/// Read all entries of the history file, oldest first. Malformed lines are skipped.
+pub fn read(year: Year) -> Vec<Entry> {
+    fs::read_to_string(path(year))
+        .map(|s| parse(&s))
+        .unwrap_or_default()
+}
+
+fn parse(s: &str) -> Vec<Entry> {
+    s.lines()
+        .filter(|l| !l.trim().is_empty())
+        .filter_map(|l| match Entry::try_from(l) {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                eprintln!("Skipping malformed history entry: {e}");
+                None
+            }
+        })
+        .collect()
+}
+
+fn git_revision() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Whether the code has uncommitted changes. Files written by `--store` are not taken into account.
+fn is_git_dirty() -> bool {
+    Command::new("git")
+        .args([
+            "status",
+            "--porcelain",
+            "--",
+            "src",
+            "Cargo.toml",
+            "Cargo.lock",
+        ])
+        .output()
+        .is_ok_and(|output| output.status.success() && !output.stdout.is_empty())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Entry> for JsonValue {
+    fn from(value: &Entry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "revision".into(),
+            value
+                .revision
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("dirty".into(), JsonValue::Boolean(value.dirty));
+        map.insert("date".into(), JsonValue::String(value.date.clone()));
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.timings.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&str> for Entry {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let revision = json
+            .get("revision")
+            .and_then(|v| v.get::<String>())
+            .cloned();
+
+        let dirty = json
+            .get("dirty")
+            .and_then(|v| v.get::<bool>().copied())
+            .ok_or("Expected entry.dirty to be a boolean.")?;
+
+        let date = json
+            .get("date")
+            .and_then(|v| v.get::<String>())
+            .cloned()
+            .ok_or("Expected entry.date to be a string.")?;
+
+        let data = json
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected entry.data to be an array.")?
+            .iter()
+            .map(Timing::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Entry {
+            revision,
+            dirty,
+            date,
+            timings: Timings { data },
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{parse, Entry};
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+    };
+
+    #[test]
+    fn roundtrips_entries() {
+        let entry = Entry {
+            revision: Some("abc123".into()),
+            dirty: true,
+            date: "2024-12-07T12:34:56Z".into(),
+            timings: Timings {
+                data: vec![Timing {
+                    day: day!(1),
+                    parse: None,
+                    part_1: Some("1ms".into()),
+                    part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_allocs: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    failure: None,
+                    total_nanos: 1_000_000_f64,
+                }],
+            },
+        };
+
+        let line = JsonValue::from(&entry).stringify().unwrap();
+        assert_eq!(line.contains('\n'), false);
+
+        let parsed = Entry::try_from(line.as_str()).unwrap();
+        assert_eq!(parsed.revision, Some("abc123".into()));
+        assert_eq!(parsed.dirty, true);
+        assert_eq!(parsed.date, "2024-12-07T12:34:56Z");
+        assert_eq!(parsed.get(day!(1)).unwrap().part_1, Some("1ms".into()));
+        assert_eq!(parsed.get(day!(2)).is_none(), true);
+    }
+
+    #[test]
+    fn skips_malformed_lines() {
+        let entries = parse(
+            "{ \"revision\": null, \"dirty\": false, \"date\": \"2024-12-07T12:34:56Z\", \"data\": [] }\n\nnot json\n{}\n",
+        );
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].revision, None);
+    }
+}
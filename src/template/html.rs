@@ -0,0 +1,232 @@
+/// Convert all `<article>` elements of a puzzle page to markdown. Pages without articles are converted as a whole.
+/// Only the small subset of HTML that is used in puzzle descriptions is supported.
+pub fn to_markdown(html: &str) -> String {
+    let tokens = tokenize(html);
+
+    let mut articles = vec![];
+    let mut index = 0;
+
+    while index < tokens.len() {
+        if matches!(&tokens[index], Token::Open(name, _) if name == "article") {
+            index += 1;
+            let mut out = String::new();
+            render(&tokens, &mut index, Some("article"), false, &mut out);
+            articles.push(out.trim().to_string());
+        } else {
+            index += 1;
+        }
+    }
+
+    if articles.is_empty() {
+        let mut out = String::new();
+        render(&tokens, &mut 0, None, false, &mut out);
+        articles.push(out.trim().to_string());
+    }
+
+    articles.join("\n\n") + "\n"
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Open(String, String),
+    Close(String),
+    Text(String),
+}
+
+fn tokenize(html: &str) -> Vec<Token> {
+    let mut tokens = vec![];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            tokens.push(Token::Text(decode_entities(rest)));
+            break;
+        };
+
+        if start > 0 {
+            tokens.push(Token::Text(decode_entities(&rest[..start])));
+        }
+
+        let Some(end) = rest[start..].find('>') else {
+            tokens.push(Token::Text(decode_entities(&rest[start..])));
+            break;
+        };
+
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            tokens.push(Token::Close(name.trim().to_ascii_lowercase()));
+        } else if !tag.starts_with('!') {
+            let tag = tag.trim_end_matches('/');
+            let (name, attrs) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+            tokens.push(Token::Open(name.to_ascii_lowercase(), attrs.to_string()));
+        }
+    }
+
+    tokens
+}
+
+/// Render tokens until the closing tag `until` is reached.
+fn render(tokens: &[Token], index: &mut usize, until: Option<&str>, pre: bool, out: &mut String) {
+    while let Some(token) = tokens.get(*index) {
+        *index += 1;
+
+        match token {
+            Token::Close(name) if Some(name.as_str()) == until => return,
+            Token::Close(_) => {}
+            Token::Text(text) if pre => out.push_str(text),
+            Token::Text(text) => {
+                let text = text.replace('\n', " ");
+                if out.is_empty() || out.ends_with('\n') {
+                    out.push_str(text.trim_start());
+                } else {
+                    out.push_str(&text);
+                }
+            }
+            Token::Open(name, _) if pre => {
+                if name == "br" {
+                    out.push('\n');
+                }
+            }
+            Token::Open(name, attrs) => render_element(tokens, index, name, attrs, out),
+        }
+    }
+}
+
+fn render_element(tokens: &[Token], index: &mut usize, name: &str, attrs: &str, out: &mut String) {
+    let mut inner = String::new();
+
+    match name {
+        "br" => out.push('\n'),
+        "h2" => {
+            render(tokens, index, Some(name), false, &mut inner);
+            out.push_str(&format!("## {}\n\n", inner.trim()));
+        }
+        "p" => {
+            render(tokens, index, Some(name), false, &mut inner);
+            out.push_str(&format!("{}\n\n", inner.trim()));
+        }
+        "pre" => {
+            render(tokens, index, Some(name), true, &mut inner);
+            out.push_str(&format!("```\n{}\n```\n\n", inner.trim_end_matches('\n')));
+        }
+        "ul" => {
+            render(tokens, index, Some(name), false, &mut inner);
+            out.push_str(&format!("{}\n", inner.trim_start()));
+        }
+        "li" => {
+            render(tokens, index, Some(name), false, &mut inner);
+            out.push_str(&format!("- {}\n", inner.trim()));
+        }
+        "code" => {
+            // NOTE: puzzles highlight answers with `<code><em>..</em></code>`, keep the emphasis outside of the code span.
+            let emphasized =
+                matches!(tokens.get(*index), Some(Token::Open(name, _)) if name == "em");
+            render(tokens, index, Some(name), true, &mut inner);
+            if emphasized {
+                out.push_str(&format!("*`{inner}`*"));
+            } else {
+                out.push_str(&format!("`{inner}`"));
+            }
+        }
+        "em" => {
+            render(tokens, index, Some(name), false, &mut inner);
+            out.push_str(&format!("*{inner}*"));
+        }
+        "a" => {
+            render(tokens, index, Some(name), false, &mut inner);
+            match attribute(attrs, "href") {
+                Some(href) => out.push_str(&format!("[{inner}]({href})")),
+                None => out.push_str(&inner),
+            }
+        }
+        "script" | "style" | "head" => render(tokens, index, Some(name), true, &mut inner),
+        "img" | "meta" | "link" | "input" | "hr" => {}
+        _ => render(tokens, index, Some(name), false, out),
+    }
+}
+
+fn attribute<'a>(attrs: &'a str, name: &str) -> Option<&'a str> {
+    let start = attrs.find(&format!("{name}=\""))? + name.len() + 2;
+    let len = attrs[start..].find('"')?;
+    Some(&attrs[start..start + len])
+}
+
+pub fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let char = match entity {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity
+                    .strip_prefix("#x")
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#')?.parse().ok())
+                    .and_then(char::from_u32),
+            }?;
+            Some((char, end))
+        });
+
+        match decoded {
+            Some((char, end)) => {
+                out.push(char);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decode_entities, to_markdown};
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            decode_entities("a &lt; b &amp;&amp; c&#62;d &#x27;e&#39;"),
+            "a < b && c>d 'e'"
+        );
+        assert_eq!(decode_entities("AT&T &unknown;"), "AT&T &unknown;");
+    }
+
+    #[test]
+    fn converts_articles() {
+        let html = r#"<html><head><title>Day 1</title></head><body>
+<main><article class="day-desc"><h2>--- Day 1: Test ---</h2><p>For example:</p>
+<pre><code>3   4
+4   3
+</code></pre>
+<ul><li>The <em>first</em> pair.</li><li>See <a href="/2024/about">about</a>.</li></ul>
+<p>The total is <code><em>11</em></code>, not <code>x &lt; y</code>.</p>
+</article><p>Ignored.</p><article><p>Part two.</p></article></main></body></html>"#;
+
+        assert_eq!(
+            to_markdown(html),
+            "## --- Day 1: Test ---\n\nFor example:\n\n```\n3   4\n4   3\n```\n\n- The *first* pair.\n- See [about](/2024/about).\n\nThe total is *`11`*, not `x < y`.\n\nPart two.\n"
+        );
+    }
+
+    #[test]
+    fn converts_pages_without_articles() {
+        assert_eq!(to_markdown("<p>Hello<br>world</p>"), "Hello\nworld\n");
+    }
+}
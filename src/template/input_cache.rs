@@ -0,0 +1,207 @@
+/// Checksums of downloaded inputs, stored in `data/<year>/inputs.json`.
+/// Inputs are only downloaded again if they are missing or a download is forced, and `cargo solve` warns about inputs that changed since they were downloaded.
+use std::{collections::HashMap, fs, io, path::Path, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::profile::{self, Profile};
+use crate::template::transaction::Transaction;
+use crate::template::{Day, Year};
+
+/// Checksums of the downloaded inputs per day, can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InputCache {
+    pub data: HashMap<Day, String>,
+}
+
+/// An input of a day. Named inputs are stored in `inputs/<day>/<name>.txt`, the name is the profile the input belongs to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Input {
+    /// `None` for the input of the default session, `inputs/<day>.txt`.
+    pub profile: Option<Profile>,
+    pub path: String,
+}
+
+impl Input {
+    pub fn name(&self) -> String {
+        self.profile
+            .as_ref()
+            .map_or("default".into(), ToString::to_string)
+    }
+}
+
+/// State of the input of a day, compared with the checksum recorded when it was downloaded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputState {
+    /// The input file does not exist or is empty.
+    Missing,
+    /// The input was not downloaded by the template, e.g. pasted by hand.
+    Unknown,
+    Unchanged,
+    Changed,
+}
+
+impl InputCache {
+    /// The file that holds the checksums of a year, per session profile.
+    pub fn path(year: Year) -> String {
+        year.data_path(&profile::scoped("inputs.json"))
+    }
+
+    /// The input file of a day. Inputs of a session profile are stored as `inputs/<day>/<profile>.txt`.
+    pub fn input_path(year: Year, day: Day) -> String {
+        match Profile::active() {
+            Some(profile) => year.data_path(&format!("inputs/{day}/{profile}.txt")),
+            None => year.data_path(&format!("inputs/{day}.txt")),
+        }
+    }
+
+    /// All non-empty inputs of a day: the input of the default session, followed by the named inputs sorted by name.
+    pub fn inputs(year: Year, day: Day) -> Vec<Input> {
+        let default = Input {
+            profile: None,
+            path: year.data_path(&format!("inputs/{day}.txt")),
+        };
+
+        let mut named: Vec<Input> = fs::read_dir(year.data_path(&format!("inputs/{day}")))
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| {
+                        let path = entry.ok()?.path();
+                        let name = path.file_name()?.to_str()?.strip_suffix(".txt")?;
+                        Some(Input {
+                            profile: Some(Profile::new(name)?),
+                            path: path.to_string_lossy().into_owned(),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        named.sort_unstable_by_key(Input::name);
+
+        std::iter::once(default)
+            .chain(named)
+            .filter(|input| fs::metadata(&input.path).is_ok_and(|m| m.len() > 0))
+            .collect()
+    }
+
+    /// Dehydrate checksums to a JSON file as part of `transaction`, so they are restored together with the inputs.
+    pub fn store_file(&self, year: Year, transaction: &mut Transaction) -> Result<(), io::Error> {
+        let mut json = vec![];
+        JsonValue::from(self).format_to(&mut json)?;
+        transaction.write(Path::new(&Self::path(year)), &json, true)
+    }
+
+    /// Rehydrate checksums from a JSON file. If not present, returns an empty cache.
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(Self::path(year))
+            .map_err(|x| x.to_string())
+            .and_then(|s| InputCache::try_from(s.as_str()))
+            .unwrap_or_default()
+    }
+
+    /// Record the checksum of a downloaded input.
+    pub fn record(&mut self, day: Day, input: &str) {
+        self.data.insert(day, checksum(input));
+    }
+
+    /// Read the input file of a day and compare it with its recorded checksum.
+    pub fn state(&self, year: Year, day: Day) -> InputState {
+        let input = fs::read_to_string(Self::input_path(year, day)).ok();
+        self.compare(day, input.as_deref())
+    }
+
+    fn compare(&self, day: Day, input: Option<&str>) -> InputState {
+        match (input, self.data.get(&day)) {
+            (None | Some(""), _) => InputState::Missing,
+            (Some(_), None) => InputState::Unknown,
+            (Some(input), Some(expected)) if checksum(input) == *expected => InputState::Unchanged,
+            (Some(_), Some(_)) => InputState::Changed,
+        }
+    }
+}
+
+/// 64-bit FNV-1a hash of the input, as hex. Good enough to detect accidental edits, not meant to be secure.
+pub fn checksum(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{hash:016x}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&InputCache> for JsonValue {
+    fn from(value: &InputCache) -> Self {
+        JsonValue::Object(
+            value
+                .data
+                .iter()
+                .map(|(day, checksum)| (day.to_string(), JsonValue::String(checksum.clone())))
+                .collect(),
+        )
+    }
+}
+
+impl TryFrom<&str> for InputCache {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON file."))?;
+
+        let days = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let mut data = HashMap::new();
+
+        for (day, checksum) in days {
+            let parsed =
+                Day::from_str(day).map_err(|_| format!("expected `{day}` to be a day."))?;
+            let checksum = checksum
+                .get::<String>()
+                .ok_or(format!("expected checksum of {day} to be a string."))?;
+            data.insert(parsed, checksum.clone());
+        }
+
+        Ok(InputCache { data })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{checksum, InputCache, InputState};
+    use crate::day;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn computes_checksums() {
+        assert_eq!(checksum(""), "cbf29ce484222325");
+        assert_eq!(checksum("a"), "af63dc4c8601ec8c");
+        assert_ne!(checksum("3   4\n"), checksum("3   4"));
+    }
+
+    #[test]
+    fn detects_changed_inputs() {
+        let mut cache = InputCache::default();
+        cache.record(day!(1), "3   4\n");
+
+        assert_eq!(
+            cache.compare(day!(1), Some("3   4\n")),
+            InputState::Unchanged
+        );
+        assert_eq!(cache.compare(day!(1), Some("3   5\n")), InputState::Changed);
+        assert_eq!(cache.compare(day!(1), Some("")), InputState::Missing);
+        assert_eq!(cache.compare(day!(1), None), InputState::Missing);
+        assert_eq!(cache.compare(day!(2), Some("1\n")), InputState::Unknown);
+    }
+
+    #[test]
+    fn roundtrips_checksums() {
+        let mut cache = InputCache::default();
+        cache.record(day!(1), "3   4\n");
+        cache.record(day!(25), "1\n");
+
+        let json = JsonValue::from(&cache).stringify().unwrap();
+        assert_eq!(InputCache::try_from(json.as_str()), Ok(cache));
+        assert!(InputCache::try_from("{\"01\": 1}").is_err());
+    }
+}
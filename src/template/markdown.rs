@@ -0,0 +1,209 @@
+/// Terminal rendering of puzzle descriptions.
+/// Supports the markdown written by the native client and by aoc-cli: headings, paragraphs, lists, code blocks and inline emphasis.
+use std::env;
+
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+const DEFAULT_WIDTH: usize = 80;
+
+/// The width to wrap text to. Uses `COLUMNS` if it is set.
+pub fn terminal_width() -> usize {
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|c| c.parse().ok())
+        .filter(|&c| c > 20)
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+/// Render markdown for the terminal, wrapping paragraphs to `width`. Emphasis is printed in bold if `styled` is set.
+pub fn render(markdown: &str, width: usize, styled: bool) -> String {
+    let mut out = String::new();
+    let mut paragraph: Vec<&str> = vec![];
+    let mut lines = markdown.lines().peekable();
+
+    let flush = |paragraph: &mut Vec<&str>, out: &mut String| {
+        if !paragraph.is_empty() {
+            out.push_str(&wrap(&paragraph.join(" "), width, "", "", styled));
+            out.push('\n');
+            paragraph.clear();
+        }
+    };
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("```") {
+            flush(&mut paragraph, &mut out);
+            for code in lines.by_ref() {
+                if code.trim().starts_with("```") {
+                    break;
+                }
+                out.push_str(&format!("    {code}\n"));
+            }
+            out.push('\n');
+        } else if let Some(heading) = trimmed.strip_prefix("## ") {
+            flush(&mut paragraph, &mut out);
+            out.push_str(&heading_line(heading, styled));
+        } else if lines
+            .peek()
+            .is_some_and(|next| next.len() > 2 && next.chars().all(|c| c == '-'))
+            && !trimmed.is_empty()
+        {
+            // NOTE: setext heading, as written by aoc-cli.
+            flush(&mut paragraph, &mut out);
+            lines.next();
+            out.push_str(&heading_line(trimmed, styled));
+        } else if let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+        {
+            flush(&mut paragraph, &mut out);
+            out.push_str(&wrap(item, width, "  • ", "    ", styled));
+            if lines.peek().is_some_and(|next| next.trim().is_empty()) {
+                out.push('\n');
+            }
+        } else if trimmed.is_empty() {
+            flush(&mut paragraph, &mut out);
+        } else {
+            paragraph.push(trimmed);
+        }
+    }
+
+    flush(&mut paragraph, &mut out);
+    out.trim_end().to_string() + "\n"
+}
+
+fn heading_line(heading: &str, styled: bool) -> String {
+    let text: String = parse_inline(heading).into_iter().map(|(c, _)| c).collect();
+    if styled {
+        format!("{ANSI_BOLD}{text}{ANSI_RESET}\n\n")
+    } else {
+        format!("{text}\n\n")
+    }
+}
+
+/// Split inline markdown into characters and whether they are emphasized.
+/// Backticks, emphasis markers, escapes and link targets are removed.
+fn parse_inline(text: &str) -> Vec<(char, bool)> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = vec![];
+    let mut emphasis = false;
+    let mut code = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\\' if !code && chars.get(i + 1).is_some_and(char::is_ascii_punctuation) => {
+                out.push((chars[i + 1], emphasis));
+                i += 1;
+            }
+            '`' => code = !code,
+            '*' | '_' if !code && (c == '*' || is_delimiter(&chars, i)) => {
+                while chars.get(i + 1) == Some(&c) {
+                    i += 1;
+                }
+                emphasis = !emphasis;
+            }
+            '[' if !code && chars[i..].windows(2).any(|w| w == [']', '(']) => {}
+            ']' if !code && chars.get(i + 1) == Some(&'(') => {
+                while i < chars.len() && chars[i] != ')' {
+                    i += 1;
+                }
+            }
+            _ => out.push((c, emphasis)),
+        }
+        i += 1;
+    }
+
+    out
+}
+
+/// Underscores only mark emphasis at the edge of a word, not inside `snake_case`.
+fn is_delimiter(chars: &[char], i: usize) -> bool {
+    let before = i.checked_sub(1).and_then(|i| chars.get(i));
+    let after = chars.get(i + 1);
+    !(before.is_some_and(|c| c.is_alphanumeric()) && after.is_some_and(|c| c.is_alphanumeric()))
+}
+
+/// Wrap inline markdown to `width`, prefixing the first line with `first` and following lines with `rest`.
+fn wrap(text: &str, width: usize, first: &str, rest: &str, styled: bool) -> String {
+    let chars = parse_inline(text);
+    let words = chars.split(|(c, _)| *c == ' ').filter(|w| !w.is_empty());
+
+    let mut lines: Vec<Vec<(char, bool)>> = vec![];
+    let mut line: Vec<(char, bool)> = vec![];
+    let mut prefix_len = first.chars().count();
+
+    for word in words {
+        if !line.is_empty() && prefix_len + line.len() + 1 + word.len() > width {
+            lines.push(std::mem::take(&mut line));
+            prefix_len = rest.chars().count();
+        }
+        if !line.is_empty() {
+            let emphasis = line.last().is_some_and(|(_, e)| *e) && word[0].1;
+            line.push((' ', emphasis));
+        }
+        line.extend_from_slice(word);
+    }
+    lines.push(line);
+
+    let mut out = String::new();
+    for (i, line) in lines.iter().enumerate() {
+        out.push_str(if i == 0 { first } else { rest });
+        let mut emphasis = false;
+        for &(c, e) in line {
+            if styled && e != emphasis {
+                out.push_str(if e { ANSI_BOLD } else { ANSI_RESET });
+                emphasis = e;
+            }
+            out.push(c);
+        }
+        if styled && emphasis {
+            out.push_str(ANSI_RESET);
+        }
+        out.push('\n');
+    }
+
+    out
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::render;
+    use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+    #[test]
+    fn renders_markdown() {
+        let markdown = "## --- Day 1: Test ---\n\nThe *first* list is `3 4` and [this](/link).\n\n```\n3   4\n4   3\n```\n\n- one\n- two\n\nA\\-b snake_case.\n";
+
+        assert_eq!(
+            render(markdown, 80, false),
+            "--- Day 1: Test ---\n\nThe first list is 3 4 and this.\n\n    3   4\n    4   3\n\n  • one\n  • two\n\nA-b snake_case.\n"
+        );
+    }
+
+    #[test]
+    fn renders_setext_headings() {
+        assert_eq!(
+            render("\\--- Day 1 ---\n----------\n\nHello\nworld.\n", 80, false),
+            "--- Day 1 ---\n\nHello world.\n"
+        );
+    }
+
+    #[test]
+    fn wraps_paragraphs() {
+        assert_eq!(
+            render("one two three four five\n\n- six seven eight\n", 12, false),
+            "one two\nthree four\nfive\n\n  • six\n    seven\n    eight\n"
+        );
+    }
+
+    #[test]
+    fn styles_emphasis() {
+        assert_eq!(
+            render("The total is *`11`*.", 80, true),
+            format!("The total is {ANSI_BOLD}11{ANSI_RESET}.\n")
+        );
+    }
+}
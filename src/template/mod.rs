@@ -1,44 +1,116 @@
-use std::{env, fs};
+use std::{env, fs, panic::Location};
 
+pub mod allocations;
 pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod profile;
+pub mod registry;
 pub mod runner;
+pub mod spans;
 
 pub use day::*;
+pub use year::*;
 
+mod answers;
+mod date;
 mod day;
+mod event;
+mod history;
+mod html;
+mod input_cache;
+mod markdown;
+mod puzzle;
 mod readme_benchmarks;
 mod run_multi;
+mod submissions;
 mod timings;
+mod transaction;
+mod year;
+
+#[cfg(all(feature = "dhat-heap", feature = "count-allocs"))]
+compile_error!("the `dhat-heap` and `count-allocs` features both install a global allocator and cannot be combined.");
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+///
+/// The year is taken from the name of the calling solution file, e.g. `src/bin/2024-01.rs`.
+/// Outside of a solution, the year set with `AOC_YEAR` is used.
 #[must_use]
+#[track_caller]
 pub fn read_file(folder: &str, day: Day) -> String {
+    read_year_file(caller_year(), folder, day)
+}
+
+/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+#[must_use]
+#[track_caller]
+pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+    read_year_file_part(caller_year(), folder, day, part)
+}
+
+/// Helper function that reads a text file of a specific year to a string.
+/// The `inputs` folder holds one input per profile, the input of the selected profile is read.
+#[must_use]
+pub fn read_year_file(year: Year, folder: &str, day: Day) -> String {
+    if folder == "inputs" {
+        return read_input(year, day);
+    }
+
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(year.data_path(&format!("{folder}/{day}.txt")));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+/// Helper function that reads a text file of a specific year to string, appending a part suffix.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_year_file_part(year: Year, folder: &str, day: Day, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+    let filepath = cwd.join(year.data_path(&format!("{folder}/{day}-{part}.txt")));
+    let f = fs::read_to_string(filepath);
+    f.expect("could not open input file")
+}
+
+/// Helper function that reads the input of a day, or the input of the selected session profile.
+#[must_use]
+pub fn read_input(year: Year, day: Day) -> String {
+    let filepath = env::current_dir()
+        .unwrap()
+        .join(input_cache::InputCache::input_path(year, day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Helper function that reads the example of a part, falling back to the example shared by both parts.
+#[must_use]
+pub fn read_example(year: Year, day: Day, part: u8) -> Option<String> {
+    let folder = env::current_dir().ok()?.join(year.data_path("examples"));
+    fs::read_to_string(folder.join(format!("{day}-{part}.txt")))
+        .or_else(|_| fs::read_to_string(folder.join(format!("{day}.txt"))))
+        .ok()
+}
+
+#[track_caller]
+fn caller_year() -> Year {
+    Year::from_path(Location::caller().file())
+        .or_else(Year::from_env)
+        .expect("could not determine the year, name the solution `src/bin/<year>-<day>.rs` or set AOC_YEAR")
+}
+
+/// Creates the constants `DAY` and `YEAR` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// The optional `parse` parameter takes a function that turns the input into a value that is shared by both parts.
+/// Parsing is timed and reported separately, the parts then receive a reference to the parsed value:
+///
+/// ```ignore
+/// advent_of_code::solution!(1, parse = parse_input);
+/// ```
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,19 +122,72 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, 1, parse = $parse:expr) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_one, 1]);
+    };
+    ($day:expr, 2, parse = $parse:expr) => {
+        $crate::solution!(@impl_parsed $day, $parse, [part_two, 2]);
+    };
 
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@setup $day);
+
+        /// Run the solution for the current day, this is also the entry point of the `registry` binary.
+        pub fn run_solution() {
+            use $crate::template::runner::*;
+            let input = $crate::template::read_input(YEAR, DAY);
+            $( run_part($func, &input, YEAR, DAY, $part, || {
+                let example = $crate::template::read_example(YEAR, DAY, $part)?;
+                $func(&example).map(|answer| answer.to_string())
+            }); )*
+        }
+    };
+
+    (@impl_parsed $day:expr, $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@setup $day);
+
+        /// Run the solution for the current day, this is also the entry point of the `registry` binary.
+        pub fn run_solution() {
+            use $crate::template::runner::*;
+            let input = $crate::template::read_input(YEAR, DAY);
+            let parsed = run_parse($parse, &input, DAY);
+            $( run_part($func, &parsed, YEAR, DAY, $part, || {
+                let example = $crate::template::read_example(YEAR, DAY, $part)?;
+                $func(&$parse(&example)).map(|answer| answer.to_string())
+            }); )*
+        }
+    };
+
+    (@setup $day:expr) => {
         /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+        pub const DAY: $crate::template::Day = $crate::day!($day);
 
-        #[cfg(feature = "dhat-heap")]
-        #[global_allocator]
-        static ALLOC: dhat::Alloc = dhat::Alloc;
+        /// The year of the current day, taken from the file name of the solution.
+        pub const YEAR: $crate::template::Year = match $crate::template::Year::from_path(file!()) {
+            Some(year) => year,
+            None => panic!("solutions need to be named `src/bin/<year>-<day>.rs`"),
+        };
+
+        const _ASSERT_DAY_IN_YEAR: () = assert!(
+            DAY.into_inner() <= YEAR.days(),
+            "the event of this year does not have this day"
+        );
 
         fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            run_solution();
         }
+
+        // NOTE: the `registry` binary links all solutions and installs its own allocator.
+        #[cfg(all(feature = "dhat-heap", not(feature = "registry")))]
+        #[global_allocator]
+        static ALLOC: dhat::Alloc = dhat::Alloc;
+
+        #[cfg(all(feature = "count-allocs", not(feature = "registry")))]
+        #[global_allocator]
+        static COUNTING_ALLOC: $crate::template::allocations::CountingAlloc =
+            $crate::template::allocations::CountingAlloc;
     };
 }
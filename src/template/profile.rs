@@ -0,0 +1,168 @@
+/// Named session profiles, for teams that share a repository but not an account.
+/// Sessions are stored outside of the repository, in `<config dir>/adventofcode/profiles/<name>.session`.
+/// Inputs, answers and submissions of a profile are stored next to the ones of the default session, e.g. `data/2024/inputs/01/alice.txt`.
+use std::{env, fmt::Display, fs, io, path::PathBuf};
+
+/// A named session profile, selected with `--profile <name>` or `AOC_PROFILE`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Profile(String);
+
+impl Profile {
+    /// Creates a [`Profile`] if the name only contains letters, digits, `-` and `_`.
+    pub fn new(name: &str) -> Option<Self> {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        valid.then(|| Self(name.to_string()))
+    }
+
+    /// The selected profile, if any.
+    pub fn active() -> Option<Self> {
+        env::var("AOC_PROFILE")
+            .ok()
+            .and_then(|name| Self::new(&name))
+    }
+
+    /// All profiles with a stored session, sorted by name.
+    pub fn list() -> Vec<Self> {
+        let mut profiles: Vec<Self> = profiles_dir()
+            .and_then(|dir| fs::read_dir(dir).ok())
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| {
+                        let name = entry.ok()?.file_name().into_string().ok()?;
+                        Self::new(name.strip_suffix(".session")?)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        profiles.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+        profiles
+    }
+
+    pub fn session_path(&self) -> Option<PathBuf> {
+        profiles_dir().map(|dir| dir.join(format!("{}.session", self.0)))
+    }
+
+    /// Store the session cookie of the profile, returns the path of the session file.
+    pub fn store_session(&self, session: &str) -> io::Result<PathBuf> {
+        let path = self
+            .session_path()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no home directory found"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        write_private(&path, session)?;
+        Ok(path)
+    }
+}
+
+impl Display for Profile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// The session cookie to use. With a selected profile, only its session file is read.
+/// Otherwise, the session is read from the same locations as aoc-cli.
+pub fn session() -> Option<String> {
+    if let Some(profile) = Profile::active() {
+        return read_session(profile.session_path()?);
+    }
+
+    if let Ok(session) = env::var("ADVENT_OF_CODE_SESSION") {
+        return Some(session.trim().to_string()).filter(|s| !s.is_empty());
+    }
+
+    [
+        home_dir().map(|h| h.join(".adventofcode.session")),
+        config_dir().map(|c| c.join("adventofcode.session")),
+    ]
+    .into_iter()
+    .flatten()
+    .find_map(read_session)
+}
+
+/// The session file of the default session, as used by aoc-cli.
+pub fn default_session_path() -> Option<PathBuf> {
+    home_dir().map(|h| h.join(".adventofcode.session"))
+}
+
+/// Scope the name of a data file to the selected profile, e.g. `answers.json` becomes `answers.alice.json`.
+pub fn scoped(file: &str) -> String {
+    scoped_to(file, Profile::active().as_ref())
+}
+
+/// Scope the name of a data file to a profile, `None` is the default session.
+pub fn scoped_to(file: &str, profile: Option<&Profile>) -> String {
+    match (profile, file.split_once('.')) {
+        (Some(profile), Some((name, extension))) => format!("{name}.{profile}.{extension}"),
+        (Some(profile), None) => format!("{file}.{profile}"),
+        (None, _) => file.to_string(),
+    }
+}
+
+/// Write a file that is only readable by the current user.
+pub fn write_private(path: &PathBuf, contents: &str) -> io::Result<()> {
+    fs::write(path, contents)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    }
+
+    Ok(())
+}
+
+fn read_session(path: PathBuf) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+fn config_dir() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|h| h.join(".config")))
+}
+
+fn profiles_dir() -> Option<PathBuf> {
+    config_dir().map(|c| c.join("adventofcode").join("profiles"))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{scoped_to, Profile};
+
+    #[test]
+    fn validates_names() {
+        assert!(Profile::new("alice").is_some());
+        assert!(Profile::new("team-b_2").is_some());
+        assert!(Profile::new("").is_none());
+        assert!(Profile::new("../alice").is_none());
+        assert!(Profile::new("a b").is_none());
+    }
+
+    #[test]
+    fn scopes_files() {
+        let alice = Profile::new("alice").unwrap();
+        assert_eq!(
+            scoped_to("answers.json", Some(&alice)),
+            "answers.alice.json"
+        );
+        assert_eq!(
+            scoped_to("submissions.jsonl", Some(&alice)),
+            "submissions.alice.jsonl"
+        );
+        assert_eq!(scoped_to("answers.json", None), "answers.json");
+    }
+}
@@ -0,0 +1,169 @@
+/// Puzzle descriptions as stored in `data/<year>/puzzles/`.
+/// Descriptions are markdown, either converted by the native client or downloaded by aoc-cli.
+use std::fs;
+
+use crate::template::{Day, Year};
+
+/// The description of a puzzle, split into its parts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Puzzle {
+    parts: Vec<String>,
+}
+
+impl Puzzle {
+    pub fn parse(markdown: &str) -> Self {
+        let mut parts = vec![String::new()];
+
+        for line in markdown.lines() {
+            if line.contains("--- Part Two ---") {
+                parts.push(String::new());
+            }
+            let part = parts.last_mut().unwrap();
+            part.push_str(line);
+            part.push('\n');
+        }
+
+        Self { parts }
+    }
+
+    /// Read the stored description of a day, if it was downloaded.
+    pub fn read(year: Year, day: Day) -> Option<Self> {
+        fs::read_to_string(Self::path(year, day))
+            .ok()
+            .map(|s| Self::parse(&s))
+    }
+
+    pub fn path(year: Year, day: Day) -> String {
+        year.data_path(&format!("puzzles/{day}.md"))
+    }
+
+    pub fn part(&self, part: u8) -> Option<&str> {
+        self.parts
+            .get(usize::from(part).checked_sub(1)?)
+            .map(String::as_str)
+    }
+
+    /// The example input of a part: the first code block that is introduced as an example.
+    /// The first part falls back to its first code block, the second part reuses the example of the first part.
+    pub fn example(&self, part: u8) -> Option<String> {
+        let blocks = code_blocks(self.part(part)?);
+
+        let example = blocks
+            .iter()
+            .find(|(intro, _)| intro.to_lowercase().contains("example"))
+            .or_else(|| blocks.first().filter(|_| part == 1))
+            .map(|(_, block)| block.clone());
+
+        match part {
+            2 => example.or_else(|| self.example(1)),
+            _ => example,
+        }
+    }
+
+    /// The answer to the example of a part: the last highlighted code in its description, e.g. `<code><em>11</em></code>`.
+    pub fn example_answer(&self, part: u8) -> Option<String> {
+        let text = self.part(part)?;
+
+        // NOTE: highlighted code is written as *`11`* by the native client and as `*11*` by some converters.
+        ["*`", "`*"]
+            .iter()
+            .zip(["`*", "*`"])
+            .filter_map(|(open, close)| {
+                let end = text.rfind(close)?;
+                let start = text[..end].rfind(open)? + open.len();
+                Some((end, &text[start..end]))
+            })
+            .filter(|(_, answer)| !answer.is_empty() && !answer.contains('\n'))
+            .max_by_key(|(end, _)| *end)
+            .map(|(_, answer)| answer.to_string())
+    }
+}
+
+/// All fenced code blocks of a text, together with the last line of text in front of them.
+fn code_blocks(markdown: &str) -> Vec<(String, String)> {
+    let mut blocks = vec![];
+    let mut intro = String::new();
+    let mut block: Option<String> = None;
+
+    for line in markdown.lines() {
+        match (&mut block, line.trim_start().starts_with("```")) {
+            (None, true) => block = Some(String::new()),
+            (None, false) if !line.trim().is_empty() => intro = line.to_string(),
+            (Some(_), true) => blocks.push((intro.clone(), block.take().unwrap())),
+            (Some(contents), false) => {
+                contents.push_str(line);
+                contents.push('\n');
+            }
+            _ => {}
+        }
+    }
+
+    blocks
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Puzzle;
+
+    const PUZZLE: &str = "## --- Day 1: Test ---
+
+Some text with `code`.
+
+```
+not the example
+```
+
+For example:
+
+```
+3   4
+4   3
+```
+
+The answer is *`11`*.
+
+## --- Part Two ---
+
+This time, consider this example:
+
+```
+1
+2
+```
+";
+
+    #[test]
+    fn splits_parts() {
+        let puzzle = Puzzle::parse(PUZZLE);
+        assert!(puzzle.part(1).unwrap().starts_with("## --- Day 1"));
+        assert!(puzzle.part(2).unwrap().starts_with("## --- Part Two"));
+        assert_eq!(puzzle.part(3), None);
+    }
+
+    #[test]
+    fn finds_examples() {
+        let puzzle = Puzzle::parse(PUZZLE);
+        assert_eq!(puzzle.example(1), Some("3   4\n4   3\n".into()));
+        assert_eq!(puzzle.example(2), Some("1\n2\n".into()));
+
+        let part_one = Puzzle::parse(PUZZLE.split("## --- Part Two").next().unwrap());
+        assert_eq!(part_one.part(2), None);
+        assert_eq!(part_one.example(2), None);
+
+        let shared = Puzzle::parse(&PUZZLE.replace("consider this example", "consider"));
+        assert_eq!(shared.example(2), Some("3   4\n4   3\n".into()));
+
+        let unlabeled = Puzzle::parse("```\n1\n```\n");
+        assert_eq!(unlabeled.example(1), Some("1\n".into()));
+    }
+
+    #[test]
+    fn finds_example_answers() {
+        let puzzle = Puzzle::parse(PUZZLE);
+        assert_eq!(puzzle.example_answer(1), Some("11".into()));
+        assert_eq!(puzzle.example_answer(2), None);
+
+        let converted = Puzzle::parse("The first is `*1*`, the answer is `*31*`.\n");
+        assert_eq!(converted.example_answer(1), Some("31".into()));
+    }
+}
@@ -2,8 +2,8 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::timings::{format_bytes, Timings};
+use crate::template::{Day, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -26,8 +26,8 @@ pub struct TablePosition {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(year: Year, day: Day) -> String {
+    format!("./{}", year.bin_path(day))
 }
 
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
@@ -52,47 +52,85 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
+/// Construct the benchmark tables, one table per year.
+fn construct_table(prefix: &str, years: Vec<(Year, Timings)>) -> String {
     let header = format!("{prefix} Benchmarks");
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let mut lines: Vec<String> = vec![MARKER.into(), header];
+
+    for (year, timings) in years {
+        let total_millis = timings.total_millis();
+        lines.push(String::new());
+        lines.push(format!("{prefix}# {year}"));
+        lines.push(String::new());
+        lines.extend(construct_year_table(year, timings));
+        lines.push(String::new());
+        lines.push(format!("**Total: {total_millis:.2}ms**"));
+    }
+
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+fn construct_year_table(year: Year, timings: Timings) -> Vec<String> {
+    // only show the memory column if allocations were counted for at least one day.
+    let has_memory = timings.data.iter().any(|t| t.peak_bytes().is_some());
+
+    let mut lines: Vec<String> = vec![];
+
+    if has_memory {
+        lines.push("| Day | Parse | Part 1 | Part 2 | Memory |".into());
+        lines.push("| :---: | :---: | :---: | :---: | :---:  |".into());
+    } else {
+        lines.push("| Day | Parse | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---: | :---:  |".into());
+    }
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+        let path = get_path_for_bin(year, timing.day);
+        let memory = if has_memory {
+            format!(
+                " `{}` |",
+                timing.peak_bytes().map_or_else(|| "-".into(), format_bytes)
+            )
+        } else {
+            String::new()
+        };
+
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | `{}` | `{}` | `{}` |{memory}",
             timing.day.into_inner(),
             path,
+            timing.parse.unwrap_or_else(|| "-".into()),
             timing.part_1.unwrap_or_else(|| "-".into()),
             timing.part_2.unwrap_or_else(|| "-".into())
         ));
     }
 
-    lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
-
-    lines.join("\n")
+    lines
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
+fn update_content(s: &mut String, years: Vec<(Year, Timings)>) -> Result<(), Error> {
     let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+    let table = construct_table("##", years);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+/// Update the benchmarks in the readme with the stored timings of every year, most recent year first.
+pub fn update() -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+
+    let mut years: Vec<(Year, Timings)> = Year::stored()
+        .into_iter()
+        .map(|year| (year, Timings::read_from_file(year)))
+        .filter(|(_, timings)| !timings.data.is_empty())
+        .collect();
+    years.sort_unstable_by_key(|(year, _)| std::cmp::Reverse(*year));
+
+    update_content(&mut readme, years)?;
     fs::write(path, &readme)?;
     Ok(())
 }
@@ -100,27 +138,56 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{
+        day,
+        template::timings::{Allocs, Timing, Timings},
+        template::Year,
+        year,
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
+                    parse: Some("1ms".into()),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_allocs: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    failure: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_allocs: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    failure: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_allocs: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    failure: None,
                     total_nanos: 9e+10,
                 },
             ],
@@ -131,28 +198,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, vec![(year!(2024), get_mock_timings())]).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, vec![(year!(2024), get_mock_timings())]).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, vec![(year!(2024), get_mock_timings())]).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, vec![(year!(2024), get_mock_timings())]).unwrap();
+        update_content(&mut s, vec![(year!(2024), get_mock_timings())]).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,24 +227,76 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, vec![(year!(2024), get_mock_timings())]).unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "### 2024",
             "",
-            "**Total: 190.00ms**",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/2024-01.rs) | `1ms` | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/2024-02.rs) | `-` | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/2024-04.rs) | `-` | `40ms` | `50ms` |",
+            "",
+            "**Total: 190000.00ms**",
             "<!--- benchmarking table --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_memory() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_allocs = Some(Allocs {
+            count: 10,
+            total_bytes: 8192,
+            peak_bytes: 2048,
+        });
+        timings.data[0].part_2_allocs = Some(Allocs {
+            count: 10,
+            total_bytes: 8192,
+            peak_bytes: 4096,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, vec![(year!(2024), timings)]).unwrap();
+
+        assert_eq!(
+            s.contains("| Day | Parse | Part 1 | Part 2 | Memory |"),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 1](./src/bin/2024-01.rs) | `1ms` | `10ms` | `20ms` | `4.0 KiB` |"),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 2](./src/bin/2024-02.rs) | `-` | `30ms` | `40ms` | `-` |"),
+            true
+        );
+    }
+
+    #[test]
+    fn format_benchmarks_per_year() {
+        let mut older = get_mock_timings();
+        older.data.truncate(1);
+
+        let years: Vec<(Year, Timings)> =
+            vec![(year!(2024), get_mock_timings()), (year!(2023), older)];
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, years).unwrap();
+
+        let year_2024 = s.find("### 2024").unwrap();
+        let year_2023 = s.find("### 2023").unwrap();
+        assert!(year_2024 < year_2023);
+        assert!(s.contains("| [Day 1](./src/bin/2023-01.rs) | `1ms` | `10ms` | `20ms` |"));
+        assert!(s.contains("**Total: 30000.00ms**"));
+        assert_eq!(s.matches("## Benchmarks").count(), 1);
+    }
 }
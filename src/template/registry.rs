@@ -0,0 +1,85 @@
+use std::{env, panic};
+
+use crate::template::{Day, Year};
+
+/// Printed before each solution runs, so the caller can tell which day was running if the process stops.
+const START_PREFIX: &str = "registry: running day ";
+
+/// A solution that is linked into the `registry` binary.
+///
+/// `build.rs` collects every `src/bin/<year>-<day>.rs` and includes it as a module of the registry binary,
+/// the `solution!` macro provides the `YEAR`, `DAY` and `run_solution` items referenced here.
+pub struct Solution {
+    pub year: Year,
+    pub day: Day,
+    pub run: fn(),
+}
+
+/// Run the solutions for the days passed as `--day <day>` arguments, or all solutions if none are passed.
+/// Solutions can be limited to a single year with `--year <year>`.
+///
+/// A panicking solution does not stop the remaining days from running, it simply emits no reports.
+/// Each day is announced with a line on stdout, see [`started_day`].
+pub fn run(solutions: &[Solution]) {
+    let args = env::args().collect::<Vec<_>>();
+    let days = days_from_args(&args);
+    let year = year_from_args(&args);
+
+    solutions
+        .iter()
+        .filter(|solution| year.is_none_or(|year| solution.year == year))
+        .filter(|solution| days.is_empty() || days.contains(&solution.day))
+        .for_each(|solution| {
+            println!("{START_PREFIX}{}", solution.day);
+            let _ = panic::catch_unwind(solution.run);
+        });
+}
+
+/// The day announced by a line of registry output, `None` if the line is not an announcement.
+pub fn started_day(line: &str) -> Option<Day> {
+    line.strip_prefix(START_PREFIX)?.parse().ok()
+}
+
+fn days_from_args(args: &[String]) -> Vec<Day> {
+    args.windows(2)
+        .filter(|pair| pair[0] == "--day")
+        .filter_map(|pair| pair[1].parse().ok())
+        .collect()
+}
+
+fn year_from_args(args: &[String]) -> Option<Year> {
+    args.windows(2)
+        .find(|pair| pair[0] == "--year")
+        .and_then(|pair| pair[1].parse().ok())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{days_from_args, started_day, year_from_args, START_PREFIX};
+    use crate::{day, year};
+
+    #[test]
+    fn reads_days_from_args() {
+        let args = ["registry", "--day", "01", "--format", "json", "--day", "12"]
+            .map(String::from)
+            .to_vec();
+        assert_eq!(days_from_args(&args), vec![day!(1), day!(12)]);
+        assert!(days_from_args(&args[..1]).is_empty());
+        assert_eq!(year_from_args(&args), None);
+    }
+
+    #[test]
+    fn reads_started_days() {
+        assert_eq!(started_day(&format!("{START_PREFIX}07")), Some(day!(7)));
+        assert_eq!(started_day("registry: running day"), None);
+        assert_eq!(started_day("07"), None);
+    }
+
+    #[test]
+    fn reads_year_from_args() {
+        let args = ["registry", "--year", "2023", "--day", "01"]
+            .map(String::from)
+            .to_vec();
+        assert_eq!(year_from_args(&args), Some(year!(2023)));
+    }
+}
@@ -1,58 +1,551 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    io,
+    path::PathBuf,
+    process::Command,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    runner::{print_report, BenchConfig, Limits, OutputFormat},
+    Day, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
 use super::{
     all_days,
-    timings::{Timing, Timings},
+    answers::{Answers, Verification},
+    input_cache::Input,
+    registry,
+    timings::{Failure, Report, Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+use child_commands::Line;
+use tinyjson::JsonValue;
+
+/// Options that control how [`run_multi`] runs days.
+#[derive(Clone, Debug)]
+pub struct RunOptions {
+    /// The year the days belong to.
+    pub year: Year,
+    pub is_release: bool,
+    pub count_allocs: bool,
+    pub format: OutputFormat,
+    /// Number of days that run concurrently.
+    pub jobs: usize,
+    pub limits: Limits,
+    /// Compare answers with the known answers in `data/<year>/answers.json`.
+    pub verify: bool,
+}
 
-    let mut need_space = false;
+/// Outcome of running multiple days.
+pub struct Summary {
+    /// Timings of all days that ran, only set if the days were benched.
+    pub timings: Option<Timings>,
+    /// Number of parts that did not produce their known answer.
+    pub mismatches: usize,
+}
 
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    bench_config: Option<&BenchConfig>,
+    options: &RunOptions,
+) -> Summary {
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    let days: Vec<Day> = all_days(options.year)
         .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
+        .collect();
+    let format = options.format;
+
+    let mut printer = DayPrinter {
+        format,
+        need_space: false,
+        answers: options
+            .verify
+            .then(|| Answers::read_from_file(options.year)),
+        mismatches: 0,
+    };
+
+    // concurrent runs compete for the CPU, which would skew benchmarks.
+    let jobs = if bench_config.is_some() && options.jobs > 1 {
+        eprintln!(
+            "Benchmarks always run one day at a time, ignoring `--jobs {}`.",
+            options.jobs
+        );
+        1
+    } else {
+        options.jobs
+    };
+
+    let executables = match child_commands::build_registry(options.is_release, options.count_allocs)
+    {
+        Ok(executable) => Executables::Registry(executable),
+        Err(_) => {
+            eprintln!(
+                "Could not build all solutions into a single binary, running each day separately. \
+                    Run `cargo build --bin registry --features registry` to see why."
+            );
+            Executables::Binaries(child_commands::build_solutions(
+                options.year,
+                &days,
+                options.is_release,
+                options.count_allocs,
+            ))
+        }
+    };
+
+    let run = Run {
+        year: options.year,
+        executables: &executables,
+        bench_config,
+        limits: &options.limits,
+    };
+
+    let timings = match &executables {
+        // without limits, all days can share a single process.
+        Executables::Registry(_) if jobs <= 1 && options.limits.is_none() => {
+            let mut timings = vec![];
+            let remaining = run_in_process(&days, &run, &mut printer, &mut timings);
+            timings.extend(run_serial(remaining, &run, &mut printer));
+            timings
+        }
+        _ if jobs <= 1 => run_serial(&days, &run, &mut printer),
+        _ => run_parallel(&days, jobs, &run, &mut printer),
+    };
+
+    let timings = bench_config.map(|_| {
+        let timings = Timings { data: timings };
+        let total_millis = timings.total_millis();
+        if format == OutputFormat::Human {
+            println!(
+                "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            );
+        }
+        timings
+    });
+
+    Summary {
+        timings,
+        mismatches: printer.mismatches,
+    }
+}
+
+/// Run the solution of a day against each of the given inputs.
+/// The answers of every input are verified with the known answers of the profile the input belongs to.
+pub fn run_inputs(day: Day, inputs: &[Input], options: &RunOptions) -> Result<Summary, Error> {
+    let executable = child_commands::build_solutions(
+        options.year,
+        &[day],
+        options.is_release,
+        options.count_allocs,
+    )
+    .remove(&day)
+    .ok_or(Error::BuildFailed)?;
+
+    let mut printer = DayPrinter {
+        format: options.format,
+        need_space: false,
+        answers: None,
+        mismatches: 0,
+    };
+
+    for input in inputs {
+        printer.header(&format!("Input {} ({})", input.name(), input.path));
+        printer.answers = Some(Answers::read_for(options.year, input.profile.as_ref()));
+
+        let mut cmd = child_commands::limited_command(&executable, &options.limits);
+        cmd.args(["--format", &OutputFormat::Json.to_string()]);
+
+        // NOTE: solutions read the input of the selected profile, see `InputCache::input_path`.
+        match &input.profile {
+            Some(profile) => cmd.env("AOC_PROFILE", profile.to_string()),
+            None => cmd.env_remove("AOC_PROFILE"),
+        };
+
+        let mut reports = vec![];
+        let failure = child_commands::run_child(cmd, options.limits.timeout, |line| {
+            child_commands::forward_line(line, options.format, |report| {
+                printer.report(&report);
+                reports.push(report);
+            });
+        })?;
+
+        printer.finish_day(day, &reports, failure);
+    }
+
+    Ok(Summary {
+        timings: None,
+        mismatches: printer.mismatches,
+    })
+}
+
+/// Executables that contain the solutions.
+enum Executables {
+    /// The `registry` binary that links all solutions.
+    Registry(PathBuf),
+    /// The binaries of the days that could be built.
+    Binaries(HashMap<Day, PathBuf>),
+}
+
+/// Everything needed to invoke the solution of a day.
+struct Run<'a> {
+    year: Year,
+    executables: &'a Executables,
+    bench_config: Option<&'a BenchConfig>,
+    limits: &'a Limits,
+}
+
+impl Run<'_> {
+    /// Command that runs the given days, `None` if there is no executable for them.
+    fn command(&self, days: &[Day]) -> Option<Command> {
+        let mut args: Vec<String> = vec![];
+
+        let executable = match self.executables {
+            Executables::Registry(executable) => {
+                args.extend(["--year".into(), self.year.to_string()]);
+                for day in days {
+                    args.extend(["--day".into(), day.to_string()]);
+                }
+                executable
             }
-            need_space = true;
+            Executables::Binaries(binaries) => binaries.get(days.first()?)?,
+        };
+
+        // solutions report their results as JSON, which is rendered in the requested format by the caller.
+        args.extend(["--format".into(), OutputFormat::Json.to_string()]);
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+        if let Some(bench_config) = self.bench_config {
+            // mirror `--time` flag and bench config to child invocations.
+            args.extend(bench_config.to_args());
+        }
+
+        let mut cmd = child_commands::limited_command(executable, self.limits);
+        cmd.args(args);
+        Some(cmd)
+    }
+}
+
+/// Run all days in one invocation of the `registry` binary.
+/// If the process stops early, returns the days that did not get to run so they can run separately.
+fn run_in_process<'a>(
+    days: &'a [Day],
+    run: &Run,
+    printer: &mut DayPrinter,
+    timings: &mut Vec<Timing>,
+) -> &'a [Day] {
+    let format = printer.format;
+
+    let Some(cmd) = run.command(days) else {
+        return &[];
+    };
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    // index of the first day that has not started yet, and the reports of the day that is running.
+    let mut next = 0;
+    let mut running: Option<(Day, Vec<Report>)> = None;
+
+    let result = child_commands::run_child(cmd, None, |line| {
+        if let Line::Stdout(text) = &line {
+            if let Some(day) = registry::started_day(text) {
+                if let Some((day, reports)) = running.take() {
+                    timings.extend(printer.finish_day(day, &reports, None));
+                }
+
+                // days run in order, days that were skipped have no solution.
+                for skipped in &days[next..days.partition_point(|d| *d < day)] {
+                    printer.day_header(*skipped);
+                    printer.finish_day(*skipped, &[], None);
+                }
+
+                printer.day_header(day);
+                next = days.partition_point(|d| *d <= day);
+                running = Some((day, vec![]));
+                return;
+            }
+        }
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+        child_commands::forward_line(line, format, |report| {
+            printer.report(&report);
+            if let Some((_, reports)) = &mut running {
+                reports.push(report);
             }
         });
+    });
+
+    let stopped = !matches!(result, Ok(None));
+    let failure = match result {
+        Ok(failure) => failure,
+        Err(e) => {
+            eprintln!("Could not run the `registry` binary ({e}).");
+            None
+        }
+    };
 
-    if is_timed {
-        let timings = Timings { data: timings };
-        let total_millis = timings.total_millis();
-        println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
-        );
-        Some(timings)
-    } else {
-        None
+    // a failure of the process belongs to the day that was running when it stopped.
+    if let Some((day, reports)) = running {
+        timings.extend(printer.finish_day(day, &reports, failure));
+    }
+
+    if stopped {
+        if next < days.len() {
+            eprintln!(
+                "The `registry` binary stopped early, running the remaining days separately."
+            );
+        }
+        return &days[next..];
+    }
+
+    for day in &days[next..] {
+        printer.day_header(*day);
+        printer.finish_day(*day, &[], None);
+    }
+
+    &[]
+}
+
+/// Run every day in its own process, one after the other.
+fn run_serial(days: &[Day], run: &Run, printer: &mut DayPrinter) -> Vec<Timing> {
+    let mut timings: Vec<Timing> = Vec::with_capacity(days.len());
+    let format = printer.format;
+
+    for day in days {
+        printer.day_header(*day);
+
+        let mut reports = vec![];
+        let mut failure = None;
+
+        if let Some(cmd) = run.command(&[*day]) {
+            let result = child_commands::run_child(cmd, run.limits.timeout, |line| {
+                child_commands::forward_line(line, format, |report| {
+                    printer.report(&report);
+                    reports.push(report);
+                });
+            });
+
+            match result {
+                Ok(day_failure) => failure = day_failure,
+                Err(e) => {
+                    printer.run_error(*day, &e);
+                    continue;
+                }
+            }
+        }
+
+        timings.extend(printer.finish_day(*day, &reports, failure));
     }
+
+    timings
+}
+
+/// Run days concurrently on `jobs` threads.
+/// The output of each day is buffered and printed in day order as soon as all previous days have finished.
+fn run_parallel(days: &[Day], jobs: usize, run: &Run, printer: &mut DayPrinter) -> Vec<Timing> {
+    type Finished = Result<Option<(Vec<Line>, Option<Failure>)>, Error>;
+
+    let mut timings: Vec<Timing> = Vec::with_capacity(days.len());
+    let mut finished: Vec<Option<Finished>> = days.iter().map(|_| None).collect();
+    let mut printed = 0;
+    let format = printer.format;
+
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let tx = tx.clone();
+            let next = &next;
+
+            scope.spawn(move || {
+                let mut index = next.fetch_add(1, Ordering::Relaxed);
+
+                while let Some(day) = days.get(index) {
+                    let result = run
+                        .command(&[*day])
+                        .map(|cmd| {
+                            let mut lines = vec![];
+                            let failure =
+                                child_commands::run_child(cmd, run.limits.timeout, |line| {
+                                    lines.push(line)
+                                })?;
+                            Ok::<_, Error>((lines, failure))
+                        })
+                        .transpose();
+
+                    tx.send((index, result)).unwrap();
+                    index = next.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        }
+
+        // NOTE: drop the original sender so the receiver ends once all workers are done.
+        drop(tx);
+
+        for (index, result) in rx {
+            finished[index] = Some(result);
+
+            while let Some(result) = finished.get_mut(printed).and_then(Option::take) {
+                let day = days[printed];
+                printer.day_header(day);
+                printed += 1;
+
+                let mut reports = vec![];
+                let mut failure = None;
+
+                let result = match result {
+                    Ok(result) => result,
+                    Err(e) => {
+                        printer.run_error(day, &e);
+                        continue;
+                    }
+                };
+
+                if let Some((lines, day_failure)) = result {
+                    for line in lines {
+                        child_commands::forward_line(line, format, |report| {
+                            printer.report(&report);
+                            reports.push(report);
+                        });
+                    }
+                    failure = day_failure;
+                }
+
+                timings.extend(printer.finish_day(day, &reports, failure));
+            }
+        }
+    });
+
+    timings
+}
+
+/// Prints the reports of each day and the framing around them.
+struct DayPrinter {
+    format: OutputFormat,
+    need_space: bool,
+    /// Known answers, if answers are verified.
+    answers: Option<Answers>,
+    mismatches: usize,
+}
+
+impl DayPrinter {
+    fn report(&mut self, report: &Report) {
+        let verification = match (&self.answers, report.part) {
+            (Some(answers), Some(part)) => {
+                Some(answers.verify(report.day, part, report.answer.as_deref()))
+            }
+            _ => None,
+        };
+
+        if let Some(Verification::Incorrect { .. }) = verification {
+            self.mismatches += 1;
+        }
+
+        print_report(report, self.format, verification.as_ref());
+    }
+
+    fn day_header(&mut self, day: Day) {
+        self.header(&format!("Day {day}"));
+    }
+
+    fn header(&mut self, title: &str) {
+        if self.format != OutputFormat::Human {
+            return;
+        }
+
+        if self.need_space {
+            println!();
+        }
+        self.need_space = true;
+
+        println!("{ANSI_BOLD}{title}{ANSI_RESET}");
+        println!("------");
+    }
+
+    /// Report a day that could not be run at all, e.g. because its process could not be spawned.
+    fn run_error(&mut self, day: Day, error: &Error) {
+        match self.format {
+            OutputFormat::Human => println!("✖ could not run: {error}"),
+            OutputFormat::Json => print_day_record(day, "error", &error.to_string()),
+        }
+    }
+
+    fn not_solved(&self) {
+        if self.format == OutputFormat::Human {
+            println!("Not solved.");
+        }
+    }
+
+    /// Report how a day ended, returning its timing if any part of it ran.
+    fn finish_day(
+        &mut self,
+        day: Day,
+        reports: &[Report],
+        failure: Option<Failure>,
+    ) -> Option<Timing> {
+        // parts with a known answer that did not report at all can not be verified.
+        if let Some(answers) = &self.answers {
+            for part in [1, 2] {
+                let Some(expected) = answers.get(day, part) else {
+                    continue;
+                };
+
+                if reports.iter().all(|r| r.part != Some(part)) {
+                    self.mismatches += 1;
+                    if self.format == OutputFormat::Human {
+                        println!("Part {part}: ✗ did not run (expected {expected})");
+                    }
+                }
+            }
+        }
+
+        match (failure, self.format) {
+            (Some(failure), OutputFormat::Human) => println!("✖ {failure}"),
+            (Some(failure), OutputFormat::Json) => {
+                print_day_record(day, "failure", &failure.to_string());
+            }
+            (None, _) if reports.is_empty() => {
+                self.not_solved();
+                return None;
+            }
+            (None, _) => {}
+        }
+
+        let mut timing = Timing::from_reports(day, reports);
+        timing.failure = failure;
+        Some(timing)
+    }
+}
+
+/// Print a JSON record about a day that did not report normally, e.g. `{"day":"01","failure":"timed out"}`.
+fn print_day_record(day: Day, key: &str, message: &str) {
+    let json = JsonValue::Object(HashMap::from([
+        ("day".into(), JsonValue::String(day.to_string())),
+        (key.into(), JsonValue::String(message.into())),
+    ]));
+    // NOTE: stringify only fails for non-finite numbers, the record only contains strings.
+    println!("{}", json.stringify().unwrap());
 }
 
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
+    BuildFailed,
     IO(io::Error),
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => f.write_str("could not read the output of the process"),
+            Error::BuildFailed => f.write_str("the build failed"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
@@ -60,198 +553,242 @@ impl From<std::io::Error> for Error {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(year: Year, day: Day) -> String {
+    format!("./{}", year.bin_path(day))
 }
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// Solutions live in isolated binaries and are additionally linked into the `registry` binary.
+/// This module encapsulates interaction with these binaries, both building and invoking them as well as collecting the reports they emit.
 pub mod child_commands {
     use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use crate::template::{
+        registry,
+        runner::{Limits, OutputFormat},
+        timings::{Failure, Report},
+        Day, Year,
+    };
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        collections::HashMap,
+        io::{BufRead, BufReader, Read},
+        path::{Path, PathBuf},
+        process::{Command, ExitStatus, Stdio},
+        sync::mpsc::{self, RecvTimeoutError, Sender},
         thread,
+        time::{Duration, Instant},
     };
+    use tinyjson::JsonValue;
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
-
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
-
-        if is_release {
-            args.push("--release");
-        }
+    /// A line of output of a child process.
+    pub enum Line {
+        Stdout(String),
+        Stderr(String),
+    }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
-        }
+    /// Build the `registry` binary that links all solutions, returning the path of the executable.
+    pub fn build_registry(is_release: bool, count_allocs: bool) -> Result<PathBuf, Error> {
+        let features = if count_allocs {
+            "registry,count-allocs"
+        } else {
+            "registry"
+        };
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // build errors are expected while a day is work in progress, they are surfaced when falling back to separate binaries.
+        build_bin("registry", Some(features), is_release, Stdio::null())
+    }
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+    /// Build the binaries of the given days, skipping days that have not been scaffolded yet or fail to build.
+    pub fn build_solutions(
+        year: Year,
+        days: &[Day],
+        is_release: bool,
+        count_allocs: bool,
+    ) -> HashMap<Day, PathBuf> {
+        days.iter()
+            .filter(|day| Path::new(&get_path_for_bin(year, **day)).exists())
+            .filter_map(|day| {
+                let features = count_allocs.then_some("count-allocs");
+                let executable =
+                    build_bin(&year.bin_name(*day), features, is_release, Stdio::inherit()).ok()?;
+                Some((*day, executable))
+            })
+            .collect()
+    }
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+    fn build_bin(
+        name: &str,
+        features: Option<&str>,
+        is_release: bool,
+        stderr: Stdio,
+    ) -> Result<PathBuf, Error> {
+        let mut args: Vec<String> = vec![
+            "build".into(),
+            "--quiet".into(),
+            "--bin".into(),
+            name.into(),
+            "--message-format".into(),
+            "json-render-diagnostics".into(),
+        ];
+
+        if let Some(features) = features {
+            args.extend(["--features".into(), features.into()]);
+        }
 
-        let mut output = vec![];
+        if is_release {
+            args.push("--release".into());
+        }
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+        let output = Command::new("cargo").args(&args).stderr(stderr).output()?;
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        if !output.status.success() {
+            return Err(Error::BuildFailed);
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
-
-        Ok(output)
+        // the path of the executable is part of the `compiler-artifact` message for the binary.
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.parse::<JsonValue>().ok())
+            .find_map(|message| {
+                let message: &HashMap<_, _> = message.get()?;
+                let target: &HashMap<_, _> = message.get("target")?.get()?;
+                let target_name: &String = target.get("name")?.get()?;
+                let path: &String = message.get("executable")?.get()?;
+                (target_name == name).then(|| PathBuf::from(path))
+            })
+            .ok_or(Error::BuildFailed)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+    /// Command that runs an executable, with the memory limit applied if one is set.
+    ///
+    /// The limit is set with `ulimit -v` in a shell that then replaces itself with the executable, so it is only supported on Linux.
+    /// Without a memory limit, the executable runs directly.
+    pub fn limited_command(executable: &Path, limits: &Limits) -> Command {
+        match limits.memory {
+            #[cfg(target_os = "linux")]
+            Some(bytes) => {
+                // NOTE: `exec` replaces the shell, so killing the child kills the solution.
+                let mut cmd = Command::new("sh");
+                cmd.arg("-c")
+                    .arg(format!("ulimit -v {} && exec \"$0\" \"$@\"", bytes / 1024))
+                    .arg(executable);
+                cmd
+            }
+            _ => Command::new(executable),
+        }
+    }
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
+    /// Run a child to completion, passing every line of output to `on_line` as it arrives.
+    ///
+    /// The child is killed if it does not emit a report within `timeout`, the timeout restarts with every report.
+    /// Returns the reason the child failed, if it was stopped by a limit or exited with an error.
+    pub fn run_child(
+        mut cmd: Command,
+        timeout: Option<Duration>,
+        mut on_line: impl FnMut(Line),
+    ) -> Result<Option<Failure>, Error> {
+        let mut child = cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+
+        let stdout = child.stdout.take().ok_or(Error::BrokenPipe)?;
+        let stderr = child.stderr.take().ok_or(Error::BrokenPipe)?;
+
+        let (tx, rx) = mpsc::channel();
+        let threads = [
+            read_lines(stdout, tx.clone(), Line::Stdout),
+            read_lines(stderr, tx, Line::Stderr),
+        ];
+
+        let mut deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let mut alloc_error = false;
+        let mut timed_out = false;
+
+        loop {
+            let received = match deadline {
+                Some(deadline) => {
+                    rx.recv_timeout(deadline.saturating_duration_since(Instant::now()))
                 }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+
+            match received {
+                Ok(line) => {
+                    match &line {
+                        Line::Stdout(line) if Report::from_line(line).is_some() => {
+                            deadline = timeout.map(|timeout| Instant::now() + timeout);
+                        }
+                        // this is how the default allocation error handler aborts.
+                        Line::Stderr(line) if line.starts_with("memory allocation of") => {
+                            alloc_error = true;
+                        }
+                        _ => {}
+                    }
+                    on_line(line);
                 }
+                Err(RecvTimeoutError::Timeout) => {
+                    child.kill()?;
+                    timed_out = true;
+                    break;
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
 
-                timings.total_nanos += nanos;
-            });
-
-        timings
+        // the readers end once the child is gone, so all of its output is handled before reporting how it ended.
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        rx.try_iter().for_each(&mut on_line);
+
+        let status = child.wait()?;
+        Ok(if timed_out {
+            Some(Failure::TimedOut)
+        } else if status.success() {
+            None
+        } else if alloc_error && aborted(&status) {
+            Some(Failure::OutOfMemory)
+        } else {
+            Some(Failure::Crashed)
+        })
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
+    /// Whether the child was stopped by `SIGABRT`, which is how the default allocation error handler ends the process.
+    #[cfg(unix)]
+    fn aborted(status: &ExitStatus) -> bool {
+        use std::os::unix::process::ExitStatusExt;
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+        const SIGABRT: i32 = 6;
+        status.signal() == Some(SIGABRT)
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    #[cfg(not(unix))]
+    fn aborted(status: &ExitStatus) -> bool {
+        !status.success()
     }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
-
-        #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
+    fn read_lines(
+        reader: impl Read + Send + 'static,
+        tx: Sender<Line>,
+        wrap: fn(String) -> Line,
+    ) -> thread::JoinHandle<()> {
+        thread::spawn(move || {
+            for line in BufReader::new(reader).lines().map_while(Result::ok) {
+                if tx.send(wrap(line)).is_err() {
+                    break;
+                }
+            }
+        })
+    }
 
-        #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+    /// Forward a line of child output to stdout/stderr, passing reports to `on_report` instead.
+    pub fn forward_line(line: Line, format: OutputFormat, mut on_report: impl FnMut(Report)) {
+        match line {
+            // the days announced by the `registry` binary are only of interest to `run_in_process`.
+            Line::Stdout(line) if registry::started_day(&line).is_some() => {}
+            Line::Stdout(line) => match Report::from_line(&line) {
+                Some(report) => on_report(report),
+                // anything else is printed by the solution itself, keep it out of machine-readable output.
+                None if format == OutputFormat::Json => eprintln!("{line}"),
+                None => println!("{line}"),
+            },
+            Line::Stderr(line) => eprintln!("{line}"),
         }
     }
 }
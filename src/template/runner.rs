@@ -1,35 +1,347 @@
 /// Encapsulates code that interacts with solution functions.
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::io::{self, stdout, IsTerminal, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use tinyjson::JsonValue;
+
+#[cfg(feature = "count-allocs")]
+use crate::template::allocations;
+use crate::template::answers::{Answers, Verification};
+use crate::template::aoc_client::{self, AocClientError};
+#[cfg(feature = "spans")]
+use crate::template::spans;
+use crate::template::spans::SpanNode;
+use crate::template::submissions::{self, Submission, Submissions, Verdict};
+use crate::template::timings::{format_bytes, Allocs, Report, Stats};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{Day, Year, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Run a part of a solution. `example` computes the answer to the example, it is only called when submitting.
+pub fn run_part<I: Copy, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    year: Year,
+    day: Day,
+    part: u8,
+    example: impl FnOnce() -> Option<String>,
+) {
+    let format = OutputFormat::from_args(&env::args().collect::<Vec<_>>());
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, duration, stats, allocs, spans) = run_timed(func, input, |result| {
+        if format == OutputFormat::Human {
+            print_result(result, &part_str, "");
+        }
+    });
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_report(
+        &Report {
+            day,
+            part: Some(part),
+            answer: result.as_ref().map(ToString::to_string),
+            nanos: stats
+                .as_ref()
+                .map_or_else(|| duration_to_nanos(&duration), |s| s.mean),
+            samples: stats.as_ref().map_or(1, |s| s.samples),
+            stats,
+            allocs,
+        },
+        format,
+        None,
+    );
+
+    if format == OutputFormat::Human {
+        print_spans(&spans, 1);
+    }
 
     if let Some(result) = result {
-        submit_result(result, day, part);
+        submit_result(&result.to_string(), year, day, part, example);
+    }
+}
+
+/// Run the parse function of a solution. Parsing is timed like a part, but does not print or submit a result.
+pub fn run_parse<'a, P>(func: impl Fn(&'a str) -> P, input: &'a str, day: Day) -> P {
+    let format = OutputFormat::from_args(&env::args().collect::<Vec<_>>());
+
+    let (parsed, duration, stats, allocs, spans) = run_timed(func, input, |_| {
+        if format == OutputFormat::Human {
+            print!("Parse:");
+        }
+    });
+
+    print_report(
+        &Report {
+            day,
+            part: None,
+            answer: None,
+            nanos: stats
+                .as_ref()
+                .map_or_else(|| duration_to_nanos(&duration), |s| s.mean),
+            samples: stats.as_ref().map_or(1, |s| s.samples),
+            stats,
+            allocs,
+        },
+        format,
+        None,
+    );
+
+    if format == OutputFormat::Human {
+        print_spans(&spans, 1);
+    }
+
+    parsed
+}
+
+/// Print the report of a part in the given output format.
+pub fn print_report(report: &Report, format: OutputFormat, verification: Option<&Verification>) {
+    match format {
+        OutputFormat::Json => {
+            let mut json = JsonValue::from(report);
+
+            if let (Some(verification), JsonValue::Object(map)) = (verification, &mut json) {
+                let verified = match verification {
+                    Verification::Correct => JsonValue::Boolean(true),
+                    Verification::Incorrect { .. } => JsonValue::Boolean(false),
+                    Verification::Unknown => JsonValue::Null,
+                };
+                map.insert("verified".into(), verified);
+            }
+
+            // NOTE: stringify only fails for non-finite numbers. Durations are finite, and `BenchConfig::validate` makes sure there is at least one sample.
+            println!("{}", json.stringify().unwrap());
+        }
+        OutputFormat::Human => {
+            let duration_str =
+                format_duration(&nanos_to_duration(report.nanos), report.stats.as_ref())
+                    + &format_allocs(report.allocs.as_ref())
+                    + &format_verification(verification);
+
+            match report.part {
+                Some(part) => print_result(&report.answer, &format!("Part {part}"), &duration_str),
+                None => {
+                    print!("\r");
+                    println!("Parse:{duration_str}");
+                }
+            }
+        }
+    }
+}
+
+fn format_verification(verification: Option<&Verification>) -> String {
+    match verification {
+        None => String::new(),
+        Some(Verification::Correct) => " ✓".into(),
+        Some(Verification::Incorrect { expected }) => format!(" ✗ (expected {expected})"),
+        Some(Verification::Unknown) => " ? (no known answer)".into(),
+    }
+}
+
+/// Print recorded spans as an indented tree.
+fn print_spans(spans: &[SpanNode], depth: usize) {
+    for span in spans {
+        let calls = if span.calls > 1 {
+            format!(" [{} calls]", span.calls)
+        } else {
+            String::new()
+        };
+
+        println!(
+            "{}{ANSI_ITALIC}{}{ANSI_RESET}: {:.1?}{calls}",
+            "  ".repeat(depth),
+            span.name,
+            span.total
+        );
+
+        print_spans(&span.children, depth + 1);
+    }
+}
+
+/// Output format of solution binaries, `cargo solve`, `cargo all` and `cargo time`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable output with colors.
+    #[default]
+    Human,
+    /// One JSON [`Report`] per line.
+    Json,
+}
+
+impl OutputFormat {
+    /// Read the format from the `--format` argument passed to a solution binary.
+    pub fn from_args(args: &[String]) -> Self {
+        args.iter()
+            .position(|x| x == "--format")
+            .and_then(|i| args.get(i + 1))
+            .and_then(|x| x.parse().ok())
+            .unwrap_or_default()
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("unknown format `{s}`, expecting `human` or `json`")),
+        }
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Human => f.write_str("human"),
+            Self::Json => f.write_str("json"),
+        }
+    }
+}
+
+/// Controls how a part is benched when the `--time` flag is passed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Duration the part is executed for before samples are taken.
+    pub warmup: Duration,
+    /// Approximate duration of execution time spent taking samples.
+    pub budget: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            warmup: Duration::ZERO,
+            budget: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10000,
+        }
+    }
+}
+
+impl BenchConfig {
+    /// Read the config from the arguments passed to a solution binary.
+    /// Returns `None` if the `--time` flag is not set. An invalid config falls back to the defaults.
+    pub fn from_args(args: &[String]) -> Option<Self> {
+        if !args.iter().any(|x| x == "--time") {
+            return None;
+        }
+
+        let value_of = |key: &str| {
+            let index = args.iter().position(|x| x == key)?;
+            args.get(index + 1)
+        };
+
+        let default = Self::default();
+
+        let config = Self {
+            warmup: value_of("--warmup")
+                .and_then(|x| parse_duration(x).ok())
+                .unwrap_or(default.warmup),
+            budget: value_of("--budget")
+                .and_then(|x| parse_duration(x).ok())
+                .unwrap_or(default.budget),
+            min_samples: value_of("--min-samples")
+                .and_then(|x| x.parse().ok())
+                .unwrap_or(default.min_samples),
+            max_samples: value_of("--max-samples")
+                .and_then(|x| x.parse().ok())
+                .unwrap_or(default.max_samples),
+        };
+
+        match config.validate() {
+            Ok(()) => Some(config),
+            Err(e) => {
+                eprintln!("Ignoring invalid bench config: {e}");
+                Some(default)
+            }
+        }
+    }
+
+    /// Check that the sample bounds allow taking at least one sample.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min_samples == 0 || self.min_samples > self.max_samples {
+            return Err("expecting `--min-samples` to be between 1 and `--max-samples`.".into());
+        }
+        Ok(())
     }
+
+    /// Build the arguments that mirror this config to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        vec![
+            "--time".into(),
+            "--warmup".into(),
+            format!("{}ns", self.warmup.as_nanos()),
+            "--budget".into(),
+            format!("{}ns", self.budget.as_nanos()),
+            "--min-samples".into(),
+            self.min_samples.to_string(),
+            "--max-samples".into(),
+            self.max_samples.to_string(),
+        ]
+    }
+}
+
+/// Limits applied to each day when running multiple days.
+/// A day that hits a limit is stopped, the remaining days still run.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// Maximum time a part may take, including benching.
+    pub timeout: Option<Duration>,
+    /// Maximum virtual memory of a day's process in bytes, only supported on Linux.
+    pub memory: Option<u64>,
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+impl Limits {
+    pub fn is_none(&self) -> bool {
+        self.timeout.is_none() && self.memory.is_none()
+    }
+}
+
+/// Parse a duration with a unit suffix, e.g. `500ms` or `2s`.
+/// Supported units are `ns`, `us` (or `µs`), `ms` and `s`.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let split = s
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(|| format!("missing unit in duration `{s}`, e.g. `500ms`"))?;
+
+    let (value, unit) = s.split_at(split);
+    let value: u64 = value
+        .parse()
+        .map_err(|_| format!("invalid duration `{s}`"))?;
+
+    match unit {
+        "ns" => Ok(Duration::from_nanos(value)),
+        "us" | "µs" => Ok(Duration::from_micros(value)),
+        "ms" => Ok(Duration::from_millis(value)),
+        "s" => Ok(Duration::from_secs(value)),
+        _ => Err(format!("unknown unit `{unit}` in duration `{s}`")),
+    }
+}
+
+/// Run a solution part. The behavior differs depending on whether the `--time` flag is passed:
+///  1. without it, the function is executed once.
+///  2. with it, the function is benched according to the [`BenchConfig`] passed (by default approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// When the `count-allocs` feature is enabled, allocations are counted during the first execution.
+/// Likewise, spans are recorded during the first execution when the `spans` feature is enabled.
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, Option<Stats>, Option<Allocs>, Vec<SpanNode>) {
+    #[cfg(feature = "count-allocs")]
+    allocations::reset();
+
+    #[cfg(feature = "spans")]
+    spans::reset();
+
     let timer = Instant::now();
     let result = {
         #[cfg(feature = "dhat-heap")]
@@ -39,25 +351,64 @@ fn run_timed<I: Copy, T>(
     };
     let base_time = timer.elapsed();
 
+    #[cfg(feature = "count-allocs")]
+    let allocs = Some(allocations::snapshot());
+    #[cfg(not(feature = "count-allocs"))]
+    let allocs = None;
+
+    #[cfg(feature = "spans")]
+    let spans = spans::take();
+    #[cfg(not(feature = "spans"))]
+    let spans = vec![];
+
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
-    };
+    let args: Vec<String> = env::args().collect();
+    let show_progress = OutputFormat::from_args(&args) == OutputFormat::Human;
 
-    (result, run.0, run.1)
+    if let Some(config) = BenchConfig::from_args(&args) {
+        let stats = bench(func, input, &base_time, &config, show_progress);
+        (
+            result,
+            nanos_to_duration(stats.mean),
+            Some(stats),
+            allocs,
+            spans,
+        )
+    } else {
+        (result, base_time, None, allocs, spans)
+    }
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    config: &BenchConfig,
+    show_progress: bool,
+) -> Stats {
+    if show_progress {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    // warm up caches and branch predictors, this also gives a better estimate than the first run.
+    let warmup_timer = Instant::now();
+    let mut warmup_runs: u32 = 0;
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    while warmup_timer.elapsed() < config.warmup {
+        black_box(func(black_box(input)));
+        warmup_runs += 1;
+    }
+
+    let estimate = if warmup_runs > 0 {
+        warmup_timer.elapsed() / warmup_runs
+    } else {
+        *base_time
+    };
+
+    let bench_iterations = (config.budget.as_nanos() / cmp::max(estimate.as_nanos(), 10))
+        .clamp(config.min_samples, config.max_samples);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -67,26 +418,99 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    summarize(&timers)
+}
+
+/// Compute summary statistics for a set of samples.
+/// Samples outside of 1.5 times the interquartile range are counted as outliers.
+#[allow(clippy::cast_precision_loss)]
+fn summarize(samples: &[Duration]) -> Stats {
+    let mut nanos: Vec<f64> = samples.iter().map(|x| x.as_nanos() as f64).collect();
+    nanos.sort_unstable_by(f64::total_cmp);
+
+    let len = nanos.len() as f64;
+    let mean = nanos.iter().sum::<f64>() / len;
+    let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / len;
+
+    let q1 = percentile(&nanos, 25.0);
+    let q3 = percentile(&nanos, 75.0);
+    let fence = (q3 - q1) * 1.5;
+
+    Stats {
+        samples: nanos.len() as u128,
+        mean,
+        min: nanos.first().copied().unwrap_or_default(),
+        median: percentile(&nanos, 50.0),
+        p95: percentile(&nanos, 95.0),
+        std_dev: variance.sqrt(),
+        outliers: nanos
+            .iter()
+            .filter(|x| **x < q1 - fence || **x > q3 + fence)
+            .count() as u128,
+    }
+}
+
+/// Linearly interpolated percentile of a sorted slice.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0_f64;
+    }
+
+    let rank = p / 100_f64 * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - rank.floor())
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn nanos_to_duration(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos as u64)
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+#[allow(clippy::cast_precision_loss)]
+fn duration_to_nanos(duration: &Duration) -> f64 {
+    duration.as_nanos() as f64
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+fn format_duration(duration: &Duration, stats: Option<&Stats>) -> String {
+    match stats {
+        None => format!(" ({duration:.1?})"),
+        Some(stats) => format!(
+            " ({duration:.1?} @ {}) [min {:.1?}, median {:.1?}, p95 {:.1?}, σ {:.1?}, {}]",
+            count(stats.samples, "sample"),
+            nanos_to_duration(stats.min),
+            nanos_to_duration(stats.median),
+            nanos_to_duration(stats.p95),
+            nanos_to_duration(stats.std_dev),
+            count(stats.outliers, "outlier")
+        ),
+    }
+}
+
+/// A count followed by a noun, pluralized unless the count is one, e.g. `1 outlier` or `3 outliers`.
+fn count(n: u128, noun: &str) -> String {
+    if n == 1 {
+        format!("{n} {noun}")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!("{n} {noun}s")
+    }
+}
+
+fn format_allocs(allocs: Option<&Allocs>) -> String {
+    match allocs {
+        None => String::new(),
+        Some(allocs) => format!(
+            " {{{} allocs, {} total, {} peak}}",
+            allocs.count,
+            format_bytes(allocs.total_bytes),
+            format_bytes(allocs.peak_bytes)
+        ),
     }
 }
 
@@ -125,18 +549,41 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
+/// Store the answer of a correctly submitted part, so `cargo all --verify` can check it later on.
+fn record_answer(year: Year, day: Day, part: u8, answer: &str, format: OutputFormat) {
+    let mut answers = Answers::read_from_file(year);
+    answers.set(day, part, answer);
+
+    match answers.store_file(year) {
+        Ok(()) => print_status(
+            format,
+            &format!(
+                "Stored answer to part {part} in \"{}\".",
+                Answers::path(year)
+            ),
+        ),
+        Err(e) => eprintln!("Failed to store answer: {e}"),
+    }
+}
+
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
+///  2. a session cookie is configured or aoc-cli is installed.
+///  3. the answer was not rejected before, see [`Submissions::check`].
+///
+/// Suspicious answers need to be confirmed in a terminal, or with the `--yes` flag. Every submission is recorded in the ledger.
+fn submit_result(
+    result: &str,
+    year: Year,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+    example: impl FnOnce() -> Option<String>,
+) {
     let args: Vec<String> = env::args().collect();
+    let format = OutputFormat::from_args(&args);
 
     if !args.contains(&"--submit".into()) {
-        return None;
+        return;
     }
 
     if args.len() < 3 {
@@ -152,14 +599,200 @@ fn submit_result<T: Display>(
     };
 
     if part_submit != part {
-        return None;
+        return;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    if let Err(refusal) = Submissions::read_from_file(year).check(day, part, result) {
+        eprintln!("Not submitting \"{result}\": {refusal}");
+        return;
+    }
+
+    let other_part = Answers::read_from_file(year)
+        .get(day, 3 - part)
+        .map(ToString::to_string);
+    let warnings = submissions::warnings(
+        result,
+        example_answer(example).as_deref(),
+        other_part.as_deref(),
+    );
+
+    if !warnings.is_empty() {
+        for warning in &warnings {
+            eprintln!("Warning: {warning}");
+        }
+
+        if !args.contains(&"--yes".into()) && !confirm("Submit anyway?") {
+            return;
+        }
+    }
+
+    print_status(format, "Submitting result...");
+    let response = match aoc_client::client(year).submit(day, part, result) {
+        Ok(response) => response,
+        Err(e) => {
+            if matches!(e, AocClientError::RateLimited { .. }) {
+                record_submission(year, day, part, result, Verdict::RateLimited, format);
+            }
+            aoc_client::print_error("Failed to submit", &e);
+            process::exit(1);
+        }
+    };
+
+    print_status(format, response.trim_end());
+    let verdict = Verdict::from_response(&response);
+
+    record_submission(year, day, part, result, verdict, format);
+
+    if verdict == Verdict::Correct {
+        record_answer(year, day, part, result, format);
+    }
+}
+
+fn record_submission(
+    year: Year,
+    day: Day,
+    part: u8,
+    answer: &str,
+    verdict: Verdict,
+    format: OutputFormat,
+) {
+    match Submissions::append(year, &Submission::new(day, part, answer, verdict)) {
+        Ok(()) => print_status(
+            format,
+            &format!(
+                "Recorded submission as {verdict} in \"{}\".",
+                Submissions::path(year)
+            ),
+        ),
+        Err(e) => eprintln!("Failed to record submission: {e}"),
+    }
+}
+
+/// Print a message about a submission. Machine-readable output only contains reports on stdout, so the message goes to stderr.
+fn print_status(format: OutputFormat, message: &str) {
+    match format {
+        OutputFormat::Human => println!("{message}"),
+        OutputFormat::Json => eprintln!("{message}"),
+    }
+}
+
+/// Compute the answer to the example. A panicking solution is treated as having no answer.
+fn example_answer(example: impl FnOnce() -> Option<String>) -> Option<String> {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let answer = panic::catch_unwind(AssertUnwindSafe(example));
+    panic::set_hook(hook);
+    answer.ok().flatten()
+}
+
+/// Ask the user for confirmation. Without a terminal attached, there is nobody to ask and the answer is no.
+fn confirm(question: &str) -> bool {
+    if !io::stdin().is_terminal() {
+        eprintln!("Not submitting without confirmation, pass `--yes` to submit anyway.");
+        return false;
+    }
+
+    // NOTE: the prompt goes to stderr, to keep it out of machine-readable output on stdout.
+    eprint!("{question} [y/N] ");
+    io::stderr().flush().unwrap();
+
+    let mut line = String::new();
+    io::stdin().read_line(&mut line).is_ok() && line.trim().eq_ignore_ascii_case("y")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{count, parse_duration, percentile, summarize, BenchConfig};
+
+    #[test]
+    fn pluralizes_counts() {
+        assert_eq!(count(0, "outlier"), "0 outliers");
+        assert_eq!(count(1, "outlier"), "1 outlier");
+        assert_eq!(count(10000, "sample"), "10000 samples");
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("2s"), Ok(Duration::from_secs(2)));
+        assert_eq!(parse_duration("10us"), Ok(Duration::from_micros(10)));
+        assert_eq!(parse_duration("10µs"), Ok(Duration::from_micros(10)));
+        assert_eq!(parse_duration("7ns"), Ok(Duration::from_nanos(7)));
+        assert!(parse_duration("500").is_err());
+        assert!(parse_duration("ms").is_err());
+        assert!(parse_duration("5m").is_err());
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn mirrors_bench_config_args() {
+        let config = BenchConfig {
+            warmup: Duration::from_millis(200),
+            budget: Duration::from_secs(5),
+            min_samples: 3,
+            max_samples: 100,
+        };
+        assert_eq!(BenchConfig::from_args(&config.to_args()), Some(config));
+        assert_eq!(BenchConfig::from_args(&["--submit".into()]), None);
+        assert_eq!(
+            BenchConfig::from_args(&["--time".into()]),
+            Some(BenchConfig::default())
+        );
+    }
+
+    #[test]
+    fn validates_bench_config() {
+        let args = |min: &str, max: &str| -> Vec<String> {
+            [
+                "--time",
+                "--budget",
+                "0ns",
+                "--min-samples",
+                min,
+                "--max-samples",
+                max,
+            ]
+            .map(String::from)
+            .to_vec()
+        };
+
+        assert_eq!(
+            BenchConfig::from_args(&args("0", "10")),
+            Some(BenchConfig::default())
+        );
+        assert_eq!(
+            BenchConfig::from_args(&args("20", "10")),
+            Some(BenchConfig::default())
+        );
+        assert_eq!(
+            BenchConfig::from_args(&args("1", "1")).map(|c| c.min_samples),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn interpolates_percentiles() {
+        let sorted = [1_f64, 2_f64, 3_f64, 4_f64];
+        assert_eq!(percentile(&sorted, 0.0), 1_f64);
+        assert_eq!(percentile(&sorted, 50.0), 2.5_f64);
+        assert_eq!(percentile(&sorted, 100.0), 4_f64);
+        assert_eq!(percentile(&[], 50.0), 0_f64);
+    }
+
+    #[test]
+    fn summarizes_samples() {
+        let mut samples: Vec<Duration> = (1..=9).map(|x| Duration::from_nanos(x * 10)).collect();
+        samples.push(Duration::from_nanos(1000));
+
+        let stats = summarize(&samples);
+
+        assert_eq!(stats.samples, 10);
+        assert_eq!(stats.mean, 145_f64);
+        assert_eq!(stats.min, 10_f64);
+        assert_eq!(stats.median, 55_f64);
+        assert_eq!(stats.outliers, 1);
+        assert!(stats.p95 > 90_f64 && stats.p95 < 1000_f64);
+        assert!(stats.std_dev > 0_f64);
+    }
 }
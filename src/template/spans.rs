@@ -0,0 +1,160 @@
+/// Lightweight timing of phases inside a solution, see the [`span!`](crate::span) macro.
+/// Spans are only recorded when the `spans` feature is enabled, which `cargo solve <day> --spans` does.
+///
+/// Spans are recorded per thread, spans entered on other threads than the one running the part are not reported.
+use std::{
+    cell::RefCell,
+    mem,
+    time::{Duration, Instant},
+};
+
+/// Aggregated timing of all executions of a span with the same name and parent.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SpanNode {
+    pub name: &'static str,
+    pub total: Duration,
+    pub calls: u64,
+    pub children: Vec<SpanNode>,
+}
+
+thread_local! {
+    static ROOTS: RefCell<Vec<SpanNode>> = const { RefCell::new(vec![]) };
+    static OPEN: RefCell<Vec<&'static str>> = const { RefCell::new(vec![]) };
+}
+
+/// Guard that records the time between its creation and drop as a span.
+#[must_use]
+pub struct Span {
+    start: Instant,
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+
+        OPEN.with_borrow_mut(|open| {
+            ROOTS.with_borrow_mut(|roots| {
+                let node = node_at(roots, open);
+                node.total += elapsed;
+                node.calls += 1;
+            });
+            open.pop();
+        });
+    }
+}
+
+/// Enter a span nested in the currently open span, prefer the [`span!`](crate::span) macro over calling this directly.
+pub fn enter(name: &'static str) -> Span {
+    OPEN.with_borrow_mut(|open| {
+        open.push(name);
+        // NOTE: create the node on enter, so spans are listed in the order they were first entered.
+        ROOTS.with_borrow_mut(|roots| {
+            node_at(roots, open);
+        });
+    });
+
+    Span {
+        start: Instant::now(),
+    }
+}
+
+/// Discard all recorded spans.
+pub fn reset() {
+    ROOTS.with_borrow_mut(Vec::clear);
+    OPEN.with_borrow_mut(Vec::clear);
+}
+
+/// Take the spans recorded since the last reset.
+pub fn take() -> Vec<SpanNode> {
+    ROOTS.with_borrow_mut(mem::take)
+}
+
+fn node_at<'a>(nodes: &'a mut Vec<SpanNode>, path: &[&'static str]) -> &'a mut SpanNode {
+    let (name, rest) = path.split_first().expect("path of a span is not empty");
+
+    let index = match nodes.iter().position(|node| node.name == *name) {
+        Some(index) => index,
+        None => {
+            nodes.push(SpanNode {
+                name,
+                ..SpanNode::default()
+            });
+            nodes.len() - 1
+        }
+    };
+
+    if rest.is_empty() {
+        &mut nodes[index]
+    } else {
+        node_at(&mut nodes[index].children, rest)
+    }
+}
+
+/// Times the rest of the enclosing scope, or the given expression, as a named span.
+///
+/// Spans are recorded when running `cargo solve <day> --spans` and printed as a tree below each part.
+/// Otherwise, the macro compiles to nothing, so benchmarks are not affected.
+///
+/// ```ignore
+/// let grid = advent_of_code::span!("parse grid", Grid::from(input));
+///
+/// for _ in 0..steps {
+///     advent_of_code::span!("step");
+///     // ...
+/// }
+/// ```
+#[macro_export]
+macro_rules! span {
+    ($name:expr) => {
+        #[cfg(feature = "spans")]
+        let _span = $crate::template::spans::enter($name);
+    };
+    ($name:expr, $body:expr) => {{
+        #[cfg(feature = "spans")]
+        let _span = $crate::template::spans::enter($name);
+        $body
+    }};
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{enter, reset, take};
+
+    #[test]
+    fn aggregates_nested_spans() {
+        reset();
+
+        {
+            let _outer = enter("outer");
+            for _ in 0..3 {
+                let _inner = enter("inner");
+            }
+            let _other = enter("other");
+        }
+        {
+            let _outer = enter("outer");
+        }
+        let _sibling = enter("sibling");
+        drop(_sibling);
+
+        let spans = take();
+        assert_eq!(
+            spans.iter().map(|s| (s.name, s.calls)).collect::<Vec<_>>(),
+            vec![("outer", 2), ("sibling", 1)]
+        );
+
+        let children = &spans[0].children;
+        assert_eq!(
+            children
+                .iter()
+                .map(|s| (s.name, s.calls))
+                .collect::<Vec<_>>(),
+            vec![("inner", 3), ("other", 1)]
+        );
+        assert!(spans[0].total >= children.iter().map(|s| s.total).sum::<Duration>());
+
+        assert!(take().is_empty());
+    }
+}
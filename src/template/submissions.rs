@@ -0,0 +1,424 @@
+/// Module that keeps an append-only ledger of submitted answers.
+/// The ledger is used to refuse answers that can not be correct before they are sent to the server.
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use tinyjson::JsonValue;
+
+use crate::template::date;
+use crate::template::{profile, Day, Year};
+
+/// Verdict of a submission, as parsed from the response of the server.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    RateLimited,
+    Unknown,
+}
+
+impl Verdict {
+    /// Parse the verdict from the response to a submission.
+    pub fn from_response(response: &str) -> Self {
+        if response.contains("That's the right answer") {
+            Verdict::Correct
+        } else if response.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if response.contains("your answer is too low") {
+            Verdict::TooLow
+        } else if response.contains("That's not the right answer") {
+            Verdict::Wrong
+        } else if response.contains("You gave an answer too recently") {
+            Verdict::RateLimited
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    /// Whether the server rejected the answer. Rate-limited submissions were never checked.
+    pub fn is_rejection(self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::Wrong => "wrong",
+            Verdict::RateLimited => "rate-limited",
+            Verdict::Unknown => "unknown",
+        };
+        write!(f, "{str}")
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too high" => Ok(Verdict::TooHigh),
+            "too low" => Ok(Verdict::TooLow),
+            "wrong" => Ok(Verdict::Wrong),
+            "rate-limited" => Ok(Verdict::RateLimited),
+            "unknown" => Ok(Verdict::Unknown),
+            _ => Err(format!("unknown verdict `{s}`.")),
+        }
+    }
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    pub date: String,
+}
+
+impl Submission {
+    /// Create a submission for the current point in time.
+    pub fn new(day: Day, part: u8, answer: &str, verdict: Verdict) -> Self {
+        Self {
+            day,
+            part,
+            answer: answer.into(),
+            verdict,
+            date: date::format_date(date::now()),
+        }
+    }
+}
+
+/// Reason to not submit an answer at all.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadyRejected { verdict: Verdict, date: String },
+    TooHigh { bound: String },
+    TooLow { bound: String },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadyRejected { verdict, date } => {
+                write!(
+                    f,
+                    "this answer was already rejected as {verdict} on {date}."
+                )
+            }
+            Refusal::TooHigh { bound } => {
+                write!(
+                    f,
+                    "this answer is not lower than {bound}, which was too high."
+                )
+            }
+            Refusal::TooLow { bound } => {
+                write!(
+                    f,
+                    "this answer is not higher than {bound}, which was too low."
+                )
+            }
+        }
+    }
+}
+
+/// Reason to double-check an answer before submitting it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Warning {
+    Zero,
+    ExampleAnswer,
+    SameAsOtherPart,
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str = match self {
+            Warning::Zero => "the answer is 0.",
+            Warning::ExampleAnswer => "the answer is the same as the answer to the example.",
+            Warning::SameAsOtherPart => "the answer is the same as the answer to the other part.",
+        };
+        write!(f, "{str}")
+    }
+}
+
+/// All submissions in the ledger, oldest first.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// The ledger file of a year, per session profile.
+    pub fn path(year: Year) -> String {
+        year.data_path(&profile::scoped("submissions.jsonl"))
+    }
+
+    /// Read all submissions of the ledger file. Malformed lines are skipped.
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(Self::path(year))
+            .map(|s| parse(&s))
+            .unwrap_or_default()
+    }
+
+    /// Append a submission to the ledger file.
+    pub fn append(year: Year, submission: &Submission) -> Result<(), io::Error> {
+        let json = JsonValue::from(submission)
+            .stringify()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(Self::path(year))?;
+
+        writeln!(file, "{json}")
+    }
+
+    /// Check an answer against earlier rejections of the same part.
+    /// Numeric answers are also checked against the lowest "too high" and highest "too low" answer.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), Refusal> {
+        let submissions = self
+            .data
+            .iter()
+            .filter(|s| s.day == day && s.part == part && s.verdict.is_rejection());
+
+        if let Some(s) = submissions.clone().find(|s| s.answer == answer) {
+            return Err(Refusal::AlreadyRejected {
+                verdict: s.verdict,
+                date: s.date.clone(),
+            });
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let bounds = submissions.filter_map(|s| Some((s, s.answer.parse::<i128>().ok()?)));
+
+        let too_high = bounds
+            .clone()
+            .filter(|(s, _)| s.verdict == Verdict::TooHigh)
+            .min_by_key(|(_, bound)| *bound);
+
+        if let Some((s, bound)) = too_high {
+            if value >= bound {
+                return Err(Refusal::TooHigh {
+                    bound: s.answer.clone(),
+                });
+            }
+        }
+
+        let too_low = bounds
+            .filter(|(s, _)| s.verdict == Verdict::TooLow)
+            .max_by_key(|(_, bound)| *bound);
+
+        if let Some((s, bound)) = too_low {
+            if value <= bound {
+                return Err(Refusal::TooLow {
+                    bound: s.answer.clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Collect warnings for suspicious answers.
+pub fn warnings(answer: &str, example: Option<&str>, other_part: Option<&str>) -> Vec<Warning> {
+    let mut warnings = vec![];
+
+    if answer.trim() == "0" {
+        warnings.push(Warning::Zero);
+    }
+
+    if example == Some(answer) {
+        warnings.push(Warning::ExampleAnswer);
+    }
+
+    if other_part == Some(answer) {
+        warnings.push(Warning::SameAsOtherPart);
+    }
+
+    warnings
+}
+
+fn parse(s: &str) -> Submissions {
+    let data = s
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .filter_map(|l| match Submission::try_from(l) {
+            Ok(submission) => Some(submission),
+            Err(e) => {
+                eprintln!("Skipping malformed submission: {e}");
+                None
+            }
+        })
+        .collect();
+
+    Submissions { data }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.to_string()),
+        );
+        map.insert("date".into(), JsonValue::String(value.date.clone()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&str> for Submission {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("Expected submission.{key} to be a string."))
+        };
+
+        let day = string("day")?;
+        let day = Day::from_str(&day).map_err(|_| format!("expected `{day}` to be a day."))?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|p| *p as u8)
+            .filter(|p| matches!(p, 1 | 2))
+            .ok_or("Expected submission.part to be 1 or 2.")?;
+
+        Ok(Submission {
+            day,
+            part,
+            answer: string("answer")?,
+            verdict: string("verdict")?.parse()?,
+            date: string("date")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{parse, warnings, Refusal, Submission, Submissions, Verdict, Warning};
+    use crate::day;
+
+    fn submission(part: u8, answer: &str, verdict: Verdict) -> Submission {
+        Submission {
+            day: day!(1),
+            part,
+            answer: answer.into(),
+            verdict,
+            date: "2024-12-01T05:00:00Z".into(),
+        }
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        assert_eq!(
+            Verdict::from_response("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too high."),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer; your answer is too low."),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            Verdict::from_response("That's not the right answer. If you're stuck, ..."),
+            Verdict::Wrong
+        );
+        assert_eq!(
+            Verdict::from_response("You gave an answer too recently; you have to wait."),
+            Verdict::RateLimited
+        );
+        assert_eq!(Verdict::from_response(""), Verdict::Unknown);
+    }
+
+    #[test]
+    fn roundtrips_submissions() {
+        let submission = submission(2, "42", Verdict::TooLow);
+        let line = JsonValue::from(&submission).stringify().unwrap();
+
+        assert_eq!(Submission::try_from(line.as_str()), Ok(submission));
+        assert_eq!(parse(&format!("{line}\nnot json\n\n")).data.len(), 1);
+    }
+
+    #[test]
+    fn refuses_rejected_answers() {
+        let submissions = Submissions {
+            data: vec![
+                submission(1, "abc", Verdict::Wrong),
+                submission(1, "100", Verdict::TooHigh),
+                submission(1, "90", Verdict::TooHigh),
+                submission(1, "10", Verdict::TooLow),
+                submission(1, "50", Verdict::RateLimited),
+            ],
+        };
+
+        assert!(matches!(
+            submissions.check(day!(1), 1, "abc"),
+            Err(Refusal::AlreadyRejected {
+                verdict: Verdict::Wrong,
+                ..
+            })
+        ));
+        assert_eq!(
+            submissions.check(day!(1), 1, "95"),
+            Err(Refusal::TooHigh { bound: "90".into() })
+        );
+        assert_eq!(
+            submissions.check(day!(1), 1, "5"),
+            Err(Refusal::TooLow { bound: "10".into() })
+        );
+        assert_eq!(submissions.check(day!(1), 1, "50"), Ok(()));
+        assert_eq!(submissions.check(day!(1), 1, "def"), Ok(()));
+        assert_eq!(submissions.check(day!(1), 2, "abc"), Ok(()));
+        assert_eq!(submissions.check(day!(2), 1, "95"), Ok(()));
+    }
+
+    #[test]
+    fn warns_about_suspicious_answers() {
+        assert_eq!(warnings("42", Some("7"), Some("13")), vec![]);
+        assert_eq!(warnings("0", None, None), vec![Warning::Zero]);
+        assert_eq!(
+            warnings("7", Some("7"), Some("7")),
+            vec![Warning::ExampleAnswer, Warning::SameAsOtherPart]
+        );
+    }
+}
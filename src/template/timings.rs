@@ -1,19 +1,39 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fmt::Display, fs, io::Error, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
-
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+use crate::template::{Day, Year};
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub parse_stats: Option<Stats>,
+    pub part_1_stats: Option<Stats>,
+    pub part_2_stats: Option<Stats>,
+    pub parse_allocs: Option<Allocs>,
+    pub part_1_allocs: Option<Allocs>,
+    pub part_2_allocs: Option<Allocs>,
+    /// Set if the day was stopped before it finished, parts that did not finish are `None`.
+    pub failure: Option<Failure>,
     pub total_nanos: f64,
 }
 
+/// Summary statistics of the samples taken while benching a single part.
+/// All durations are stored as nanoseconds.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stats {
+    pub samples: u128,
+    pub mean: f64,
+    pub min: f64,
+    pub median: f64,
+    pub p95: f64,
+    pub std_dev: f64,
+    pub outliers: u128,
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
@@ -22,27 +42,40 @@ pub struct Timings {
 }
 
 impl Timings {
+    /// The file that holds the timings of a year.
+    pub fn path(year: Year) -> String {
+        year.data_path("timings.json")
+    }
+
     /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(Self::path(year))?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(Self::path(year))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// A day that failed keeps the stored timings of the parts that did not finish this time.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let mut timing = timing.clone();
+            if let (Some(_), Some(stored)) = (
+                &timing.failure,
+                self.data.iter().find(|t| t.day == timing.day),
+            ) {
+                timing.fill_from(stored);
+            }
+            data.push(timing);
         }
 
         for timing in &self.data {
@@ -51,7 +84,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -65,6 +98,253 @@ impl Timings {
             .iter()
             .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
     }
+
+    /// Compare `new` against `self`, yielding a delta for every part that is timed in both.
+    pub fn compare(&self, new: &Self) -> Vec<Delta> {
+        let mut deltas: Vec<Delta> = vec![];
+
+        for timing in &new.data {
+            let Some(stored) = self.data.iter().find(|t| t.day == timing.day) else {
+                continue;
+            };
+
+            for (part, old_nanos, new_nanos) in [
+                ("Parse", stored.parse_nanos(), timing.parse_nanos()),
+                ("Part 1", stored.part_1_nanos(), timing.part_1_nanos()),
+                ("Part 2", stored.part_2_nanos(), timing.part_2_nanos()),
+            ] {
+                if let (Some(old_nanos), Some(new_nanos)) = (old_nanos, new_nanos) {
+                    deltas.push(Delta {
+                        day: timing.day,
+                        part,
+                        old_nanos,
+                        new_nanos,
+                    });
+                }
+            }
+        }
+
+        deltas.sort_by_key(|d| d.day);
+        deltas
+    }
+}
+
+impl Timing {
+    /// Collect the reports of a solution run into a timing.
+    /// Parts without an answer are considered unsolved and are not timed.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn from_reports(day: Day, reports: &[Report]) -> Self {
+        let mut timing = Timing {
+            day,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            parse_allocs: None,
+            part_1_allocs: None,
+            part_2_allocs: None,
+            failure: None,
+            total_nanos: 0_f64,
+        };
+
+        for report in reports.iter().filter(|r| r.day == day) {
+            let duration = Some(format!("{:.1?}", Duration::from_nanos(report.nanos as u64)));
+
+            match report.part {
+                None => {
+                    timing.parse = duration;
+                    timing.parse_stats.clone_from(&report.stats);
+                    timing.parse_allocs.clone_from(&report.allocs);
+                }
+                Some(_) if report.answer.is_none() => continue,
+                Some(1) => {
+                    timing.part_1 = duration;
+                    timing.part_1_stats.clone_from(&report.stats);
+                    timing.part_1_allocs.clone_from(&report.allocs);
+                }
+                Some(2) => {
+                    timing.part_2 = duration;
+                    timing.part_2_stats.clone_from(&report.stats);
+                    timing.part_2_allocs.clone_from(&report.allocs);
+                }
+                Some(_) => continue,
+            }
+
+            timing.total_nanos += report.nanos;
+        }
+
+        timing
+    }
+
+    /// Take the timings of the steps that did not finish from `stored`.
+    fn fill_from(&mut self, stored: &Timing) {
+        if self.parse.is_none() {
+            self.total_nanos += stored.parse_nanos().unwrap_or_default();
+            self.parse.clone_from(&stored.parse);
+            self.parse_stats.clone_from(&stored.parse_stats);
+            self.parse_allocs.clone_from(&stored.parse_allocs);
+        }
+        if self.part_1.is_none() {
+            self.total_nanos += stored.part_1_nanos().unwrap_or_default();
+            self.part_1.clone_from(&stored.part_1);
+            self.part_1_stats.clone_from(&stored.part_1_stats);
+            self.part_1_allocs.clone_from(&stored.part_1_allocs);
+        }
+        if self.part_2.is_none() {
+            self.total_nanos += stored.part_2_nanos().unwrap_or_default();
+            self.part_2.clone_from(&stored.part_2);
+            self.part_2_stats.clone_from(&stored.part_2_stats);
+            self.part_2_allocs.clone_from(&stored.part_2_allocs);
+        }
+    }
+
+    pub fn parse_nanos(&self) -> Option<f64> {
+        Self::nanos(self.parse.as_deref(), self.parse_stats.as_ref())
+    }
+
+    pub fn part_1_nanos(&self) -> Option<f64> {
+        Self::nanos(self.part_1.as_deref(), self.part_1_stats.as_ref())
+    }
+
+    pub fn part_2_nanos(&self) -> Option<f64> {
+        Self::nanos(self.part_2.as_deref(), self.part_2_stats.as_ref())
+    }
+
+    /// Highest peak memory usage of all parts, if allocations were counted.
+    pub fn peak_bytes(&self) -> Option<u64> {
+        [&self.parse_allocs, &self.part_1_allocs, &self.part_2_allocs]
+            .into_iter()
+            .filter_map(|a| a.as_ref().map(|a| a.peak_bytes))
+            .max()
+    }
+
+    /// Prefer the exact mean of the stats over the rounded duration string.
+    fn nanos(duration: Option<&str>, stats: Option<&Stats>) -> Option<f64> {
+        stats.map(|s| s.mean).or_else(|| parse_nanos(duration?))
+    }
+}
+
+/// Change in execution time of a single part between two sets of timings.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Delta {
+    pub day: Day,
+    pub part: &'static str,
+    pub old_nanos: f64,
+    pub new_nanos: f64,
+}
+
+impl Delta {
+    /// Relative change in percent, positive values are slowdowns.
+    pub fn percent(&self) -> f64 {
+        if self.old_nanos == 0_f64 {
+            return 0_f64;
+        }
+        (self.new_nanos - self.old_nanos) / self.old_nanos * 100_f64
+    }
+
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.percent() > threshold
+    }
+}
+
+fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+    s.split(postfix).next()?.parse().ok()
+}
+
+const BYTE_UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+/// Format a number of bytes with a binary unit, e.g. `4.5 KiB`.
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1024_f64 && unit < BYTE_UNITS.len() - 1 {
+        value /= 1024_f64;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", BYTE_UNITS[unit])
+    }
+}
+
+/// Parse a `Debug` formatted duration to nanoseconds.
+pub fn parse_nanos(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s {
+        s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
+/// Outcome of running the parse step or a part of a solution.
+/// Solution binaries emit one report per line when invoked with `--format json`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Report {
+    pub day: Day,
+    /// The part number, `None` for the parse step.
+    pub part: Option<u8>,
+    pub answer: Option<String>,
+    /// Execution time in nanoseconds, the mean of all samples if the part was benched.
+    pub nanos: f64,
+    pub samples: u128,
+    pub stats: Option<Stats>,
+    pub allocs: Option<Allocs>,
+}
+
+impl Report {
+    /// Parse a line of solution output, returns `None` if it is not a report.
+    pub fn from_line(line: &str) -> Option<Self> {
+        let json = JsonValue::from_str(line.trim()).ok()?;
+        Report::try_from(&json).ok()
+    }
+}
+
+/// Reason a day was stopped before it finished.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Failure {
+    TimedOut,
+    OutOfMemory,
+    /// The process exited with an error, e.g. because it aborted or called `process::exit`.
+    Crashed,
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::TimedOut => f.write_str("timed out"),
+            Self::OutOfMemory => f.write_str("out of memory"),
+            Self::Crashed => f.write_str("crashed"),
+        }
+    }
+}
+
+impl FromStr for Failure {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "timed out" => Ok(Self::TimedOut),
+            "out of memory" => Ok(Self::OutOfMemory),
+            "crashed" => Ok(Self::Crashed),
+            _ => Err(format!("unknown failure `{s}`")),
+        }
+    }
+}
+
+/// Heap allocations made during a single execution of a part.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Allocs {
+    pub count: u64,
+    pub total_bytes: u64,
+    pub peak_bytes: u64,
 }
 
 /* -------------------------------------------------------------------------- */
@@ -114,6 +394,14 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
+        map.insert(
+            "parse".into(),
+            value
+                .parse
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
         let part_1 = value.part_1.clone().map(JsonValue::String);
         let part_2 = value.part_2.clone().map(JsonValue::String);
 
@@ -133,6 +421,48 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "parse_stats".into(),
+            value
+                .parse_stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "part_1_stats".into(),
+            value
+                .part_1_stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "part_2_stats".into(),
+            value
+                .part_2_stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        for (key, allocs) in [
+            ("parse_allocs", &value.parse_allocs),
+            ("part_1_allocs", &value.part_1_allocs),
+            ("part_2_allocs", &value.part_2_allocs),
+        ] {
+            map.insert(
+                key.into(),
+                allocs.as_ref().map_or(JsonValue::Null, JsonValue::from),
+            );
+        }
+
+        map.insert(
+            "failure".into(),
+            value
+                .failure
+                .map_or(JsonValue::Null, |f| JsonValue::String(f.to_string())),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -166,10 +496,50 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: parse timings and stats were added later on, timings stored before that do not have them.
+        let parse = json.get("parse").and_then(|v| v.get::<String>()).cloned();
+
+        let parse_stats = match json.get("parse_stats") {
+            Some(v) if !v.is_null() => Some(Stats::try_from(v)?),
+            _ => None,
+        };
+
+        let part_1_stats = match json.get("part_1_stats") {
+            Some(v) if !v.is_null() => Some(Stats::try_from(v)?),
+            _ => None,
+        };
+
+        let part_2_stats = match json.get("part_2_stats") {
+            Some(v) if !v.is_null() => Some(Stats::try_from(v)?),
+            _ => None,
+        };
+
+        let failure = match json.get("failure") {
+            Some(v) if !v.is_null() => Some(
+                v.get::<String>()
+                    .and_then(|v| Failure::from_str(v).ok())
+                    .ok_or("Expected timing.failure to be null or a failure.")?,
+            ),
+            _ => None,
+        };
+
+        let allocs = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => Allocs::try_from(v).map(Some),
+            _ => Ok(None),
+        };
+
         Ok(Timing {
             day,
+            parse,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            parse_stats,
+            part_1_stats,
+            part_2_stats,
+            parse_allocs: allocs("parse_allocs")?,
+            part_1_allocs: allocs("part_1_allocs")?,
+            part_2_allocs: allocs("part_2_allocs")?,
+            failure,
             total_nanos,
         })
     }
@@ -177,6 +547,198 @@ impl TryFrom<&JsonValue> for Timing {
 
 /* -------------------------------------------------------------------------- */
 
+impl From<&Stats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("mean".into(), JsonValue::Number(value.mean));
+        map.insert("min".into(), JsonValue::Number(value.min));
+        map.insert("median".into(), JsonValue::Number(value.median));
+        map.insert("p95".into(), JsonValue::Number(value.p95));
+        map.insert("std_dev".into(), JsonValue::Number(value.std_dev));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        Ok(Stats {
+            samples: number("samples")? as u128,
+            mean: number("mean")?,
+            min: number("min")?,
+            median: number("median")?,
+            p95: number("p95")?,
+            std_dev: number("std_dev")?,
+            outliers: number("outliers")? as u128,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Report> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Report) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "part".into(),
+            value
+                .part
+                .map_or(JsonValue::Null, |p| JsonValue::Number(f64::from(p))),
+        );
+        map.insert(
+            "answer".into(),
+            value
+                .answer
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "stats".into(),
+            value
+                .stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "allocs".into(),
+            value
+                .allocs
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Report {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected report.day to be a Day struct.")?;
+
+        let part = match json.get("part") {
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<f64>()
+                    .map(|p| *p as u8)
+                    .ok_or("Expected report.part to be null or a number.")?,
+            ),
+            None => return Err("Expected report to have key `part`.".into()),
+        };
+
+        let answer = json.get("answer").and_then(|v| v.get::<String>()).cloned();
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected report.nanos to be a number.")?;
+
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected report.samples to be a number.")? as u128;
+
+        let stats = match json.get("stats") {
+            Some(v) if !v.is_null() => Some(Stats::try_from(v)?),
+            _ => None,
+        };
+
+        let allocs = match json.get("allocs") {
+            Some(v) if !v.is_null() => Some(Allocs::try_from(v)?),
+            _ => None,
+        };
+
+        Ok(Report {
+            day,
+            part,
+            answer,
+            nanos,
+            samples,
+            stats,
+            allocs,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Allocs> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Allocs) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("count".into(), JsonValue::Number(value.count as f64));
+        map.insert(
+            "total_bytes".into(),
+            JsonValue::Number(value.total_bytes as f64),
+        );
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Allocs {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected allocs to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|v| v as u64)
+                .ok_or(format!("Expected allocs.{key} to be a number."))
+        };
+
+        Ok(Allocs {
+            count: number("count")?,
+            total_bytes: number("total_bytes")?,
+            peak_bytes: number("peak_bytes")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 #[cfg(feature = "test_lib")]
 mod tests {
     use crate::day;
@@ -188,20 +750,44 @@ mod tests {
             data: vec![
                 Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_allocs: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    failure: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_allocs: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    failure: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_allocs: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    failure: None,
                     total_nanos: 4e+10,
                 },
             ],
@@ -209,7 +795,10 @@ mod tests {
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::timings::{Allocs, Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
@@ -220,9 +809,49 @@ mod tests {
             assert_eq!(timing.day, day!(1));
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
+            assert_eq!(timing.parse, None);
+            assert_eq!(timing.part_1_stats, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1.0ms", "part_2": null, "part_1_stats": { "samples": 10, "mean": 1000000, "min": 900000, "median": 950000, "p95": 1200000, "std_dev": 50000, "outliers": 1 }, "total_nanos": 1000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.as_ref().unwrap();
+            assert_eq!(stats.samples, 10);
+            assert_eq!(stats.median, 950_000_f64);
+            assert_eq!(stats.outliers, 1);
+            assert_eq!(timing.part_2_stats, None);
+        }
+
+        #[test]
+        fn handles_json_parse_timings() {
+            let json = r#"{ "data": [{ "day": "01", "parse": "12.0µs", "part_1": "1ms", "part_2": "2ms", "total_nanos": 3012000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parse, Some("12.0µs".to_string()));
+            assert_eq!(timing.parse_stats, None);
+        }
+
+        #[test]
+        fn handles_json_allocs() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "part_1_allocs": { "count": 3, "total_bytes": 4096, "peak_bytes": 2048 }, "total_nanos": 1000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(
+                timing.part_1_allocs,
+                Some(Allocs {
+                    count: 3,
+                    total_bytes: 4096,
+                    peak_bytes: 2048
+                })
+            );
+            assert_eq!(timing.part_2_allocs, None);
+            assert_eq!(timing.peak_bytes(), Some(2048));
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -247,6 +876,7 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::timings::{Failure, Timing};
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
@@ -266,6 +896,20 @@ mod tests {
                 3
             );
         }
+
+        #[test]
+        fn roundtrips_failures() {
+            let mut timing = get_mock_timings().data[2].clone();
+            timing.failure = Some(Failure::TimedOut);
+
+            let value = JsonValue::from(&timing);
+            let parsed = Timing::try_from(&value).unwrap();
+            assert_eq!(parsed.failure, Some(Failure::TimedOut));
+
+            let value = JsonValue::from(&get_mock_timings().data[0]);
+            let parsed = Timing::try_from(&value).unwrap();
+            assert_eq!(parsed.failure, None);
+        }
     }
 
     mod is_day_complete {
@@ -279,13 +923,21 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_allocs: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    failure: None,
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -293,13 +945,21 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_allocs: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    failure: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -307,20 +967,207 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_allocs: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    failure: None,
                     total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+    }
+
+    mod bytes {
+        use crate::template::timings::format_bytes;
+
+        #[test]
+        fn formats_bytes() {
+            assert_eq!(format_bytes(0), "0 B");
+            assert_eq!(format_bytes(1023), "1023 B");
+            assert_eq!(format_bytes(4608), "4.5 KiB");
+            assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+        }
+    }
+
+    mod reports {
+        use tinyjson::JsonValue;
+
+        use crate::{
+            day,
+            template::timings::{Allocs, Report, Stats, Timing},
+        };
+
+        fn report(part: Option<u8>, answer: Option<&str>, nanos: f64) -> Report {
+            Report {
+                day: day!(1),
+                part,
+                answer: answer.map(ToString::to_string),
+                nanos,
+                samples: 1,
+                stats: None,
+                allocs: None,
+            }
+        }
+
+        #[test]
+        fn roundtrips_reports() {
+            let report = Report {
+                day: day!(7),
+                part: Some(2),
+                answer: Some("@ @ @ ( ) ms\n(2s @ 5 samples)".into()),
+                nanos: 74.13,
+                samples: 100,
+                stats: Some(Stats {
+                    samples: 100,
+                    mean: 74.13,
+                    ..Default::default()
+                }),
+                allocs: Some(Allocs {
+                    count: 1,
+                    total_bytes: 8,
+                    peak_bytes: 8,
+                }),
+            };
+
+            let line = JsonValue::from(&report).stringify().unwrap();
+            assert_eq!(Report::from_line(&line), Some(report));
+        }
+
+        #[test]
+        fn ignores_other_lines() {
+            assert_eq!(
+                Report::from_line("Part 1: 0 (74.13ns @ 100000 samples)"),
+                None
+            );
+            assert_eq!(Report::from_line("{ \"foo\": 1 }"), None);
+            assert_eq!(Report::from_line("[1, 2]"), None);
+            assert_eq!(
+                Report::from_line(
+                    "{ \"day\": \"01\", \"part\": null, \"nanos\": 5, \"samples\": 1 }"
+                ),
+                Some(report(None, None, 5_f64))
+            );
+        }
+
+        #[test]
+        fn ignores_solution_output_that_looks_like_reports() {
+            assert_eq!(
+                Report::from_line("Part 1: @ @ @ ( ) ms (2s @ 5 samples)"),
+                None
+            );
+            assert_eq!(
+                Report::from_line("{ \"day\": \"01\", \"answer\": \"@ @ @ ( ) ms\" }"),
+                None
+            );
+            assert_eq!(
+                Report::from_line(
+                    "{ \"day\": \"01\", \"part\": 1, \"nanos\": \"2s\", \"samples\": 5 }"
+                ),
+                None
+            );
+            assert_eq!(
+                Report::from_line("{ \"day\": \"26\", \"part\": 1, \"nanos\": 5, \"samples\": 1 }"),
+                None
+            );
+        }
+
+        #[test]
+        fn collects_timings() {
+            let timing = Timing::from_reports(
+                day!(1),
+                &[
+                    report(None, None, 1000_f64),
+                    report(Some(1), Some("10"), 74.13),
+                    report(Some(2), Some("20"), 74_130_000_f64),
+                ],
+            );
+            assert_eq!(timing.parse, Some("1.0µs".into()));
+            assert_eq!(timing.part_1, Some("74.0ns".into()));
+            assert_eq!(timing.part_2, Some("74.1ms".into()));
+            assert_eq!(timing.total_nanos, 74_131_074.13);
+        }
+
+        #[test]
+        fn skips_unsolved_parts() {
+            let timing = Timing::from_reports(
+                day!(1),
+                &[
+                    report(Some(1), Some("10"), 2_000_000_000_f64),
+                    report(Some(2), None, 100_f64),
+                ],
+            );
+            assert_eq!(timing.part_1, Some("2.0s".into()));
+            assert_eq!(timing.part_2, None);
+            assert_eq!(timing.total_nanos, 2_000_000_000_f64);
+        }
+    }
+
+    mod compare {
+        use crate::{
+            day,
+            template::timings::{Stats, Timings},
+        };
+
+        use super::get_mock_timings;
+
+        #[test]
+        fn compares_overlapping_parts() {
+            let stored = get_mock_timings();
+            let mut new = get_mock_timings();
+            new.data.remove(1);
+            new.data[0].part_1 = Some("12ms".into());
+            new.data[1].part_1 = Some("10ms".into());
+
+            let deltas = stored.compare(&new);
+
+            // day 2 is not in the new timings, day 4 has no part 2 in the stored timings.
+            assert_eq!(deltas.len(), 3);
+            assert_eq!(deltas[0].day, day!(1));
+            assert_eq!(deltas[0].part, "Part 1");
+            assert_eq!(deltas[0].percent(), 20_f64);
+            assert_eq!(deltas[0].is_regression(10_f64), true);
+            assert_eq!(deltas[0].is_regression(25_f64), false);
+            assert_eq!(deltas[1].part, "Part 2");
+            assert_eq!(deltas[1].percent(), 0_f64);
+            assert_eq!(deltas[2].day, day!(4));
+            assert_eq!(deltas[2].part, "Part 1");
+            assert_eq!(deltas[2].percent(), -75_f64);
+        }
+
+        #[test]
+        fn prefers_stats_mean() {
+            let stored = get_mock_timings();
+            let mut new = get_mock_timings();
+            new.data[0].part_1_stats = Some(Stats {
+                samples: 10,
+                mean: 5_000_000_f64,
+                ..Default::default()
+            });
+
+            let deltas = stored.compare(&new);
+            assert_eq!(deltas[0].new_nanos, 5_000_000_f64);
+            assert_eq!(deltas[0].percent(), -50_f64);
+        }
+
+        #[test]
+        fn handles_empty_stored_timings() {
+            let deltas = Timings::default().compare(&get_mock_timings());
+            assert_eq!(deltas.len(), 0);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{Failure, Timing, Timings},
         };
 
         use super::get_mock_timings;
@@ -331,8 +1178,16 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_allocs: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    failure: None,
                     total_nanos: 0_f64,
                 }],
             };
@@ -351,8 +1206,16 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_allocs: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    failure: None,
                     total_nanos: 0_f64,
                 }],
             };
@@ -365,6 +1228,35 @@ mod tests {
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn keeps_stored_parts_of_failed_days() {
+            let timings = get_mock_timings();
+
+            let other = Timings {
+                data: vec![Timing {
+                    day: day!(2),
+                    parse: None,
+                    part_1: Some("35ms".into()),
+                    part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_allocs: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    failure: Some(Failure::TimedOut),
+                    total_nanos: 35_000_000_f64,
+                }],
+            };
+            let merged = timings.merge(&other);
+
+            let day_2 = &merged.data[1];
+            assert_eq!(day_2.failure, Some(Failure::TimedOut));
+            assert_eq!(day_2.part_1, Some("35ms".into()));
+            assert_eq!(day_2.part_2, Some("40ms".into()));
+            assert_eq!(day_2.total_nanos, 75_000_000_f64);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
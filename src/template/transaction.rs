@@ -0,0 +1,116 @@
+/// Files written by a command that has several steps, e.g. `cargo scaffold --download`.
+/// If a later step fails, the changes of the earlier steps are undone.
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// A change to the file system that can be undone.
+enum Change {
+    CreatedDir(PathBuf),
+    Created(PathBuf),
+    /// An overwritten file with its previous contents.
+    Overwritten(PathBuf, Vec<u8>),
+}
+
+/// Changes written so far, in the order they were made.
+#[derive(Default)]
+pub struct Transaction {
+    changes: Vec<Change>,
+}
+
+impl Transaction {
+    /// Write a file, remembering how to undo it. Existing files are only replaced if `overwrite` is set.
+    pub fn write(&mut self, path: &Path, contents: &[u8], overwrite: bool) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            self.create_dirs(parent)?;
+        }
+
+        let previous = fs::read(path).ok();
+        if previous.is_some() && !overwrite {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "file already exists",
+            ));
+        }
+
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+
+        self.changes.push(match previous {
+            Some(previous) => Change::Overwritten(path.to_path_buf(), previous),
+            None => Change::Created(path.to_path_buf()),
+        });
+
+        file.write_all(contents)
+    }
+
+    fn create_dirs(&mut self, dir: &Path) -> io::Result<()> {
+        if dir.as_os_str().is_empty() || dir.exists() {
+            return Ok(());
+        }
+        if let Some(parent) = dir.parent() {
+            self.create_dirs(parent)?;
+        }
+        fs::create_dir(dir)?;
+        self.changes.push(Change::CreatedDir(dir.to_path_buf()));
+        Ok(())
+    }
+
+    /// Undo all changes, most recent first.
+    pub fn rollback(self) {
+        for change in self.changes.into_iter().rev() {
+            match change {
+                Change::Created(path) => {
+                    if fs::remove_file(&path).is_ok() {
+                        println!("Removed \"{}\"", path.display());
+                    }
+                }
+                Change::Overwritten(path, previous) => {
+                    if fs::write(&path, previous).is_ok() {
+                        println!("Restored \"{}\"", path.display());
+                    }
+                }
+                Change::CreatedDir(dir) => {
+                    let _ = fs::remove_dir(dir);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::Transaction;
+
+    #[test]
+    fn rolls_back_transactions() {
+        let dir = env::temp_dir().join(format!("aoc-transaction-{}", process::id()));
+        let existing = dir.join("existing.txt");
+        let created = dir.join("new").join("created.txt");
+
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&existing, "input").unwrap();
+
+        let mut transaction = Transaction::default();
+        assert!(transaction.write(&existing, b"", false).is_err());
+        transaction.write(&created, b"", false).unwrap();
+        // NOTE: a file that was created in the same transaction is removed, not restored.
+        transaction.write(&created, b"module", true).unwrap();
+        transaction.write(&existing, b"replaced", true).unwrap();
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "replaced");
+
+        transaction.rollback();
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "input");
+        assert!(!created.exists());
+        assert!(!dir.join("new").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
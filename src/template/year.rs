@@ -0,0 +1,237 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::{env, fs};
+
+use crate::template::date;
+use crate::template::event::{days_in_event, FIRST_YEAR};
+use crate::template::Day;
+
+/// A year of advent, starting with the first event in 2015.
+///
+/// The year namespaces the data of a day and the name of its binary, e.g. `data/2024/inputs/01.txt` and `src/bin/2024-01.rs`.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2024).unwrap();
+/// assert_eq!(year.to_string(), "2024")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if !(FIRST_YEAR..=9999).contains(&year) {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub const fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// The number of days of the event. Events up to 2024 have 25 days, later events have 12.
+    pub const fn days(self) -> u8 {
+        days_in_event(self.0)
+    }
+
+    /// Whether the event of this year has the given day.
+    pub fn has_day(self, day: Day) -> bool {
+        day.into_inner() <= self.days()
+    }
+
+    /// The unix timestamp at which a day of this year's event unlocks, midnight EST (UTC-5).
+    /// See: <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>
+    #[allow(clippy::cast_sign_loss)]
+    pub fn unlock_time(self, day: Day) -> u64 {
+        let year = i64::from(self.0);
+        let era = year / 400;
+        let yoe = year - era * 400;
+        // NOTE: the day of the year, counted from the 1st of march.
+        let doy = (153 * 9 + 2) / 5 + i64::from(day.into_inner()) - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        let days = era * 146_097 + doe - 719_468;
+
+        days as u64 * 86400 + 5 * 3600
+    }
+
+    /// Whether a day of this year's event is unlocked already.
+    pub fn is_unlocked(self, day: Day) -> bool {
+        date::now() >= self.unlock_time(day)
+    }
+
+    /// The year set with the `AOC_YEAR` environment variable, see `.cargo/config.toml`.
+    pub fn from_env() -> Option<Self> {
+        env::var("AOC_YEAR").ok()?.parse().ok()
+    }
+
+    /// The year of the most recent event. Events start on the 1st of december.
+    pub fn latest() -> Self {
+        let (year, month, _) = date::civil_date(date::now());
+        let year = if month == 12 { year } else { year - 1 };
+        Self(u16::try_from(year).unwrap_or(FIRST_YEAR))
+    }
+
+    /// Parses the year from the path of a solution, e.g. `src/bin/2024-01.rs`.
+    /// Used by the `solution!` macro and [`read_file`](crate::template::read_file) with the path of the calling file.
+    pub const fn from_path(path: &str) -> Option<Self> {
+        let bytes = path.as_bytes();
+
+        // find the start of the file name.
+        let mut start = bytes.len();
+        while start > 0 && bytes[start - 1] != b'/' && bytes[start - 1] != b'\\' {
+            start -= 1;
+        }
+
+        if bytes.len() < start + 5 || bytes[start + 4] != b'-' {
+            return None;
+        }
+
+        let mut year: u16 = 0;
+        let mut i = start;
+        while i < start + 4 {
+            if !bytes[i].is_ascii_digit() {
+                return None;
+            }
+            year = year * 10 + (bytes[i] - b'0') as u16;
+            i += 1;
+        }
+
+        if year < FIRST_YEAR {
+            return None;
+        }
+
+        Some(Self(year))
+    }
+
+    /// All years that have a data directory, e.g. `data/2024/`.
+    pub fn stored() -> Vec<Self> {
+        let mut years: Vec<Self> = fs::read_dir("data")
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse().ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+        years.sort_unstable();
+        years
+    }
+
+    /// The path of a file in the data directory of this year, e.g. `data/2024/inputs/01.txt`.
+    pub fn data_path(self, path: &str) -> String {
+        format!("data/{self}/{path}")
+    }
+
+    /// The name of the binary of a day in this year, e.g. `2024-01`.
+    pub fn bin_name(self, day: Day) -> String {
+        format!("{self}-{day}")
+    }
+
+    /// The path of the solution of a day in this year, e.g. `src/bin/2024-01.rs`.
+    pub fn bin_path(self, day: Day) -> String {
+        format!("src/bin/{}.rs", self.bin_name(day))
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of advent, starting with 2015")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015 && $year <= 9999,
+            concat!(
+                "invalid year `",
+                $year,
+                "`, expecting a year of advent, starting with 2015"
+            ),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Year;
+    use crate::day;
+
+    #[test]
+    fn parses_years_from_paths() {
+        assert_eq!(Year::from_path("src/bin/2024-01.rs"), Some(Year(2024)));
+        assert_eq!(
+            Year::from_path("C:\\aoc\\src\\bin\\2015-25.rs"),
+            Some(Year(2015))
+        );
+        assert_eq!(Year::from_path("src/bin/01.rs"), None);
+        assert_eq!(Year::from_path("src/bin/1999-01.rs"), None);
+        assert_eq!(Year::from_path("2024"), None);
+    }
+
+    #[test]
+    fn namespaces_paths() {
+        let year = Year(2024);
+        assert_eq!(year.data_path("inputs/01.txt"), "data/2024/inputs/01.txt");
+        assert_eq!(year.bin_name(day!(1)), "2024-01");
+        assert_eq!(year.bin_path(day!(1)), "src/bin/2024-01.rs");
+        assert_eq!("2014".parse::<Year>().ok(), None);
+    }
+
+    #[test]
+    fn knows_event_length() {
+        assert_eq!(Year(2015).days(), 25);
+        assert_eq!(Year(2024).days(), 25);
+        assert_eq!(Year(2025).days(), 12);
+        assert!(Year(2024).has_day(day!(25)));
+        assert!(!Year(2025).has_day(day!(13)));
+    }
+
+    #[test]
+    fn knows_unlock_times() {
+        assert_eq!(Year(2024).unlock_time(day!(1)), 1_733_029_200);
+        assert_eq!(Year(2015).unlock_time(day!(25)), 1_451_019_600);
+        assert!(Year(2015).is_unlocked(day!(1)));
+        assert!(!Year(9999).is_unlocked(day!(1)));
+    }
+}